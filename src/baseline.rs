@@ -0,0 +1,253 @@
+use crate::constants::read_error_code;
+use crate::diagnostic::Diagnostic;
+use crate::rule_engine::{file_definition_ranges, qualified_symbol};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bumped when the layout of the file changes.
+const BASELINE_VERSION: u32 = 1;
+
+/// File, rule code and qualified symbol, line numbers are left out so
+/// unrelated edits do not bring accepted problems back.
+type Key = (String, String, String);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    entries: Vec<BaselineEntry>,
+}
+
+/// Accepted problems, `count` of them for the same key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub file: String,
+    pub code: String,
+    pub symbol: String,
+    pub count: usize,
+}
+
+/// Problems accepted with `--write-baseline`, file paths are relative to
+/// the directory of the baseline file.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    root: PathBuf,
+    entries: BTreeMap<Key, usize>,
+}
+
+impl Baseline {
+    /// Records the problems of `results`, unreadable files are not
+    /// accepted.
+    pub fn from_results(path: &Path, results: &[(String, Vec<Diagnostic>)]) -> Baseline {
+        let mut baseline = Baseline {
+            root: root_of(path),
+            entries: BTreeMap::new(),
+        };
+        for (file, output) in results {
+            let definitions = file_definition_ranges(file);
+            for diagnostic in output {
+                if diagnostic.code == read_error_code() {
+                    continue;
+                }
+                let key = baseline.key(file, diagnostic, &definitions);
+                *baseline.entries.entry(key).or_default() += 1;
+            }
+        }
+        baseline
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("could not read the baseline {}: {}", path.display(), err))?;
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .map_err(|err| format!("invalid baseline {}: {}", path.display(), err))?;
+        if file.version != BASELINE_VERSION {
+            return Err(format!(
+                "unsupported baseline version {} in {}, expected {}",
+                file.version,
+                path.display(),
+                BASELINE_VERSION
+            ));
+        }
+        let mut entries = BTreeMap::new();
+        for entry in file.entries {
+            *entries
+                .entry((entry.file, entry.code, entry.symbol))
+                .or_default() += entry.count;
+        }
+        Ok(Baseline {
+            root: root_of(path),
+            entries,
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            entries: self.entries().collect(),
+        };
+        let mut contents = serde_json::to_string_pretty(&file).map_err(|err| err.to_string())?;
+        contents.push('\n');
+        fs::write(path, contents)
+            .map_err(|err| format!("could not write the baseline {}: {}", path.display(), err))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = BaselineEntry> + '_ {
+        self.entries
+            .iter()
+            .map(|((file, code, symbol), count)| BaselineEntry {
+                file: file.clone(),
+                code: code.clone(),
+                symbol: symbol.clone(),
+                count: *count,
+            })
+    }
+
+    /// Drops the accepted problems from `results` and returns the entries
+    /// that no longer match anything, only for the files that were linted.
+    pub fn filter(
+        &self,
+        results: Vec<(String, Vec<Diagnostic>)>,
+    ) -> (Vec<(String, Vec<Diagnostic>)>, Vec<BaselineEntry>) {
+        let mut remaining = self.entries.clone();
+        let mut linted = Vec::new();
+        let results = results
+            .into_iter()
+            .map(|(file, output)| {
+                linted.push(self.relative(&file));
+                if output.is_empty() {
+                    return (file, output);
+                }
+                let definitions = file_definition_ranges(&file);
+                let output = output
+                    .into_iter()
+                    .filter(|diagnostic| {
+                        let key = self.key(&file, diagnostic, &definitions);
+                        match remaining.get_mut(&key) {
+                            Some(count) if *count > 0 => {
+                                *count -= 1;
+                                false
+                            }
+                            _ => true,
+                        }
+                    })
+                    .collect();
+                (file, output)
+            })
+            .collect();
+
+        let stale = remaining
+            .into_iter()
+            .filter(|((file, _, _), count)| *count > 0 && linted.contains(file))
+            .map(|((file, code, symbol), count)| BaselineEntry {
+                file,
+                code,
+                symbol,
+                count,
+            })
+            .collect();
+        (results, stale)
+    }
+
+    fn key(
+        &self,
+        file: &str,
+        diagnostic: &Diagnostic,
+        definitions: &[(String, usize, usize)],
+    ) -> Key {
+        (
+            self.relative(file),
+            diagnostic.code.clone(),
+            qualified_symbol(definitions, diagnostic),
+        )
+    }
+
+    /// `file` relative to the baseline, with `/` separators so the file can
+    /// be shared across platforms.
+    fn relative(&self, file: &str) -> String {
+        let path = Path::new(file);
+        let absolute = path
+            .canonicalize()
+            .or_else(|_| std::path::absolute(path))
+            .unwrap_or_else(|_| path.to_path_buf());
+        let relative = absolute.strip_prefix(&self.root).unwrap_or(&absolute);
+        relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn root_of(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())
+}
+
+////////// Tests
+
+#[test]
+pub fn test_baseline_round_trip() {
+    let dir = std::env::temp_dir().join(format!("vipyrdocs-baseline-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let module = dir.join("module.py");
+    fs::write(
+        &module,
+        "class Class1:\n    \"\"\"Docstring.\"\"\"\n\n    def method_1(self):\n        pass\n",
+    )
+    .unwrap();
+    let file = module.to_str().unwrap().to_string();
+    let diagnostic = Diagnostic::new(
+        "D010".to_string(),
+        String::new(),
+        "method_1",
+        (4, 4),
+        (4, 4),
+    )
+    .with_file(Some(&file));
+
+    let path = dir.join("baseline.json");
+    let results = vec![(file.clone(), vec![diagnostic.clone()])];
+    Baseline::from_results(&path, &results)
+        .write(&path)
+        .unwrap();
+    let baseline = Baseline::load(&path).unwrap();
+    assert_eq!(
+        baseline.entries().collect::<Vec<_>>(),
+        vec![BaselineEntry {
+            file: "module.py".to_string(),
+            code: "D010".to_string(),
+            symbol: "Class1.method_1".to_string(),
+            count: 1,
+        }]
+    );
+
+    // moved down by a few lines, still accepted
+    let moved = Diagnostic {
+        line: 9,
+        end_line: 9,
+        ..diagnostic
+    };
+    fs::write(
+        &module,
+        "\n\n\n\n\nclass Class1:\n    \"\"\"Docstring.\"\"\"\n\n    def method_1(self):\n        pass\n",
+    )
+    .unwrap();
+    let (results, stale) = baseline.filter(vec![(file.clone(), vec![moved])]);
+    assert!(results[0].1.is_empty());
+    assert!(stale.is_empty());
+
+    let (_, stale) = baseline.filter(vec![(file, Vec::new())]);
+    assert_eq!(stale.len(), 1);
+    let (_, stale) = baseline.filter(Vec::new());
+    assert!(stale.is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,691 @@
+use crate::constants::{all_rules, normalize_code, opt_in_rules};
+use crate::docstring::DocstringStyle;
+use glob::{MatchOptions, Pattern};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{ImDocument, Item, Table};
+
+/// Files searched for, in order, in every directory from the linted path up
+/// to the filesystem root. `pyproject.toml` only counts if it has a
+/// `[tool.vipyrdocs]` table.
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["vipyrdocs.toml", "pyproject.toml"];
+
+pub const DEFAULT_TEST_FILE_PATTERNS: [&str; 2] = ["test_*", "conftest.py"];
+
+/// Tool caches, virtual environments and build output, skipped unless
+/// `exclude` is set.
+pub const DEFAULT_EXCLUDE: [&str; 21] = [
+    ".bzr",
+    ".direnv",
+    ".eggs",
+    ".git",
+    ".hg",
+    ".mypy_cache",
+    ".nox",
+    ".pants.d",
+    ".pytest_cache",
+    ".pytype",
+    ".ruff_cache",
+    ".svn",
+    ".tox",
+    ".venv",
+    "__pypackages__",
+    "_build",
+    "buck-out",
+    "build",
+    "dist",
+    "node_modules",
+    "venv",
+];
+
+/// Stubs (`*.pyi`) are only linted when added to `include`.
+pub const DEFAULT_INCLUDE: [&str; 1] = ["*.py"];
+
+/// `*` stops at `/`, use `**` to cross directories.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+const KNOWN_KEYS: [&str; 10] = [
+    "convention",
+    "select",
+    "extend-select",
+    "ignore",
+    "include",
+    "extend-include",
+    "exclude",
+    "extend-exclude",
+    "per-file-ignores",
+    "test-file-patterns",
+];
+
+/// Settings from the `[tool.vipyrdocs]` table, merged with the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// The file the settings were read from, relative patterns are resolved
+    /// against its directory.
+    pub path: Option<PathBuf>,
+    /// The canonical directory of `path`, worked out once when loading.
+    pub root: Option<PathBuf>,
+    /// The docstring convention, `None` detects it for every docstring.
+    pub convention: Option<DocstringStyle>,
+    /// Rule codes or prefixes to enable, `None` enables every rule.
+    pub select: Option<Vec<String>>,
+    /// Rule codes or prefixes to enable on top of `select`.
+    pub extend_select: Vec<String>,
+    /// Rule codes or prefixes to disable.
+    pub ignore: Vec<String>,
+    /// Glob patterns of the files to lint when discovering a directory.
+    pub include: Vec<Pattern>,
+    /// Glob patterns of files to lint on top of `include`.
+    pub extend_include: Vec<Pattern>,
+    /// Glob patterns of files and directories to skip.
+    pub exclude: Vec<Pattern>,
+    /// Glob patterns of files and directories to skip on top of `exclude`.
+    pub extend_exclude: Vec<Pattern>,
+    /// Glob pattern to rule codes or prefixes ignored in matching files.
+    pub per_file_ignores: Vec<(Pattern, Vec<String>)>,
+    /// Glob patterns of files treated as test files.
+    pub test_file_patterns: Vec<Pattern>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: None,
+            root: None,
+            convention: None,
+            select: None,
+            extend_select: Vec::new(),
+            ignore: Vec::new(),
+            include: to_patterns(&DEFAULT_INCLUDE),
+            extend_include: Vec::new(),
+            exclude: to_patterns(&DEFAULT_EXCLUDE),
+            extend_exclude: Vec::new(),
+            per_file_ignores: Vec::new(),
+            test_file_patterns: to_patterns(&DEFAULT_TEST_FILE_PATTERNS),
+        }
+    }
+}
+
+fn to_patterns(patterns: &[&str]) -> Vec<Pattern> {
+    patterns
+        .iter()
+        .map(|pattern| Pattern::new(pattern).unwrap())
+        .collect()
+}
+
+/// A problem with a configuration file, `line` is 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl Config {
+    /// Finds the nearest configuration file for `start`, which can be a file
+    /// or a directory.
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+        let first_dir = if start.is_dir() {
+            Some(start.as_path())
+        } else {
+            start.parent()
+        };
+
+        for dir in first_dir?.ancestors() {
+            for name in CONFIG_FILE_NAMES {
+                let candidate = dir.join(name);
+                if !candidate.is_file() {
+                    continue;
+                }
+                if name != "pyproject.toml" || has_tool_table(&candidate) {
+                    return Some(candidate);
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds the configuration file shared by `paths`. Paths that would each
+    /// find a different one are an error, one set of settings applies to the
+    /// whole run.
+    pub fn find_common(paths: &[PathBuf]) -> Result<Option<PathBuf>, String> {
+        let mut found: Vec<Option<PathBuf>> = Vec::new();
+        for path in paths {
+            let config = Config::find(path);
+            if !found.contains(&config) {
+                found.push(config);
+            }
+        }
+        if found.len() > 1 {
+            let names: Vec<String> = found
+                .iter()
+                .map(|config| match config {
+                    Some(config) => config.display().to_string(),
+                    None => "none".to_string(),
+                })
+                .collect();
+            return Err(format!(
+                "the paths use different configuration files ({}), pass --config or check them separately",
+                names.join(", ")
+            ));
+        }
+        Ok(found.pop().flatten())
+    }
+
+    /// Reads the settings from a `vipyrdocs.toml` (top level keys) or a
+    /// `pyproject.toml` (`[tool.vipyrdocs]` table).
+    pub fn load(path: &Path) -> Result<Config, Vec<ConfigError>> {
+        let contents = fs::read_to_string(path).map_err(|err| {
+            vec![ConfigError {
+                path: path.to_path_buf(),
+                line: None,
+                message: format!("could not read the file: {}", err),
+            }]
+        })?;
+        Config::parse(&contents, path)
+    }
+
+    pub fn parse(contents: &str, path: &Path) -> Result<Config, Vec<ConfigError>> {
+        let document = ImDocument::parse(contents).map_err(|err| {
+            vec![ConfigError {
+                path: path.to_path_buf(),
+                line: err.span().map(|span| line_of(contents, span.start)),
+                message: format!("invalid TOML: {}", err.message()),
+            }]
+        })?;
+
+        let mut config = Config {
+            path: Some(path.to_path_buf()),
+            root: Some(root_of(path)),
+            ..Config::default()
+        };
+        let is_pyproject = path
+            .file_name()
+            .is_some_and(|name| name == "pyproject.toml");
+        let table = if is_pyproject {
+            match document
+                .get("tool")
+                .and_then(|tool| tool.get("vipyrdocs"))
+                .and_then(Item::as_table_like)
+            {
+                Some(_) => document["tool"]["vipyrdocs"].as_table(),
+                None => return Ok(config),
+            }
+        } else {
+            Some(document.as_table())
+        };
+        let Some(table) = table else {
+            return Err(vec![ConfigError {
+                path: path.to_path_buf(),
+                line: None,
+                message: "[tool.vipyrdocs] should be a table".to_string(),
+            }]);
+        };
+
+        let mut reader = TableReader {
+            contents,
+            path,
+            errors: Vec::new(),
+        };
+        reader.read(table, &mut config);
+        if reader.errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(reader.errors)
+        }
+    }
+
+    /// Whether `code` is turned on by `select` or `extend-select` and not
+    /// turned off by `ignore`. The longest matching selector wins, on a tie
+    /// `ignore` wins. Opt-in rules need a selector other than `ALL`.
+    pub fn is_enabled(&self, code: &str) -> bool {
+        let selected = match &self.select {
+            Some(select) => longest_match(select, code),
+            None => Some(0),
+        };
+        let selected = selected.max(longest_match(&self.extend_select, code));
+        let selected = match selected {
+            Some(0) if opt_in_rules().iter().any(|rule| rule == code) => None,
+            selected => selected,
+        };
+        let ignored = longest_match(&self.ignore, code);
+        match (selected, ignored) {
+            (Some(selected), Some(ignored)) => selected > ignored,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Whether `code` is ignored for `file` through `per-file-ignores`.
+    pub fn is_ignored_for_file(&self, file: &str, code: &str) -> bool {
+        let mut patterns = self
+            .per_file_ignores
+            .iter()
+            .filter(|(_, codes)| longest_match(codes, code).is_some())
+            .map(|(pattern, _)| pattern)
+            .peekable();
+        if patterns.peek().is_none() {
+            return false;
+        }
+        self.candidate(file).matches_any(patterns)
+    }
+
+    /// Whether discovery skips `file`, a file or a directory. Directories
+    /// are checked on the way down, so only the name of `file` itself is
+    /// tried besides the whole path.
+    pub fn is_excluded(&self, file: &str) -> bool {
+        self.candidate(file)
+            .matches_any(self.exclude.iter().chain(&self.extend_exclude))
+    }
+
+    /// Whether discovery lints `file`, files given explicitly are always
+    /// linted.
+    pub fn is_included(&self, file: &str) -> bool {
+        self.candidate(file)
+            .matches_any(self.include.iter().chain(&self.extend_include))
+    }
+
+    pub fn is_test_file(&self, file: &str) -> bool {
+        self.candidate(file).matches_any(&self.test_file_patterns)
+    }
+
+    /// `file` in the forms the patterns are matched against: the path as
+    /// given, the path relative to the configuration file and the file name.
+    fn candidate<'a>(&self, file: &'a str) -> Candidate<'a> {
+        let path = Path::new(file);
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let relative = self.root.as_ref().and_then(|root| {
+            // `--stdin-filename` can name a file that does not exist yet
+            let absolute = path
+                .canonicalize()
+                .or_else(|_| std::path::absolute(path))
+                .ok()?;
+            absolute.strip_prefix(root).ok().map(Path::to_path_buf)
+        });
+        Candidate {
+            path,
+            relative,
+            name: path.file_name().and_then(|name| name.to_str()),
+        }
+    }
+}
+
+struct Candidate<'a> {
+    path: &'a Path,
+    relative: Option<PathBuf>,
+    name: Option<&'a str>,
+}
+
+impl Candidate<'_> {
+    fn matches_any<'p>(&self, patterns: impl IntoIterator<Item = &'p Pattern>) -> bool {
+        patterns.into_iter().any(|pattern| {
+            pattern.matches_path_with(self.path, MATCH_OPTIONS)
+                || self
+                    .relative
+                    .as_ref()
+                    .is_some_and(|relative| pattern.matches_path_with(relative, MATCH_OPTIONS))
+                || self.name.is_some_and(|name| pattern.matches(name))
+        })
+    }
+}
+
+/// The canonical directory of the configuration file `path`.
+fn root_of(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    dir.canonicalize()
+        .or_else(|_| std::path::absolute(dir))
+        .unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Length of the longest selector that is a prefix of `code`.
+fn longest_match(selectors: &[String], code: &str) -> Option<usize> {
+    selectors
+        .iter()
+        .filter_map(|selector| {
+            if selector == "ALL" {
+                Some(0)
+            } else if code.starts_with(selector.as_str()) {
+                Some(selector.len())
+            } else {
+                None
+            }
+        })
+        .max()
+}
+
+fn has_tool_table(path: &Path) -> bool {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return false;
+    };
+    let Ok(document) = ImDocument::parse(contents) else {
+        // report the broken file instead of silently skipping it
+        return true;
+    };
+    document
+        .get("tool")
+        .and_then(|tool| tool.get("vipyrdocs"))
+        .is_some()
+}
+
+fn line_of(contents: &str, offset: usize) -> usize {
+    contents[..offset.min(contents.len())].matches('\n').count() + 1
+}
+
+struct TableReader<'a> {
+    contents: &'a str,
+    path: &'a Path,
+    errors: Vec<ConfigError>,
+}
+
+impl TableReader<'_> {
+    fn error(&mut self, span: Option<std::ops::Range<usize>>, message: String) {
+        self.errors.push(ConfigError {
+            path: self.path.to_path_buf(),
+            line: span.map(|span| line_of(self.contents, span.start)),
+            message,
+        });
+    }
+
+    fn read(&mut self, table: &Table, config: &mut Config) {
+        for (name, _) in table.iter() {
+            let (key, item) = table.get_key_value(name).unwrap();
+            match name {
+                "convention" => config.convention = self.read_convention(item),
+                "select" => config.select = Some(self.read_codes(name, item)),
+                "extend-select" => config.extend_select = self.read_codes(name, item),
+                "ignore" => config.ignore = self.read_codes(name, item),
+                "include" => config.include = self.read_patterns(name, item),
+                "extend-include" => config.extend_include = self.read_patterns(name, item),
+                "exclude" => config.exclude = self.read_patterns(name, item),
+                "extend-exclude" => config.extend_exclude = self.read_patterns(name, item),
+                "test-file-patterns" => config.test_file_patterns = self.read_patterns(name, item),
+                "per-file-ignores" => config.per_file_ignores = self.read_per_file_ignores(item),
+                _ => self.error(
+                    key.span(),
+                    format!(
+                        "unknown key \"{}\", expected one of: {}",
+                        name,
+                        KNOWN_KEYS.join(", ")
+                    ),
+                ),
+            }
+        }
+    }
+
+    fn read_strings(
+        &mut self,
+        name: &str,
+        item: &Item,
+    ) -> Vec<(String, Option<std::ops::Range<usize>>)> {
+        let Some(array) = item.as_array() else {
+            self.error(
+                item.span(),
+                format!("\"{}\" should be a list of strings", name),
+            );
+            return Vec::new();
+        };
+        let mut strings = Vec::new();
+        for value in array.iter() {
+            match value.as_str() {
+                Some(string) => strings.push((string.to_string(), value.span())),
+                None => self.error(
+                    value.span(),
+                    format!("\"{}\" should only contain strings", name),
+                ),
+            }
+        }
+        strings
+    }
+
+    fn read_convention(&mut self, item: &Item) -> Option<DocstringStyle> {
+        let style = item.as_str().and_then(DocstringStyle::from_name);
+        if style.is_none() {
+            self.error(
+                item.span(),
+                format!(
+                    "\"convention\" should be one of: {}",
+                    DocstringStyle::NAMES.join(", ")
+                ),
+            );
+        }
+        style
+    }
+
+    fn read_codes(&mut self, name: &str, item: &Item) -> Vec<String> {
+        let rules = all_rules();
+        let mut codes = Vec::new();
+        for (code, span) in self.read_strings(name, item) {
+            let normalized = normalize_code(&code);
+            if normalized == "ALL" || rules.iter().any(|(rule, _)| rule.starts_with(&normalized)) {
+                codes.push(normalized);
+            } else {
+                self.error(
+                    span,
+                    format!("unknown rule code or prefix \"{}\" in \"{}\"", code, name),
+                );
+            }
+        }
+        codes
+    }
+
+    fn read_patterns(&mut self, name: &str, item: &Item) -> Vec<Pattern> {
+        let mut patterns = Vec::new();
+        for (pattern, span) in self.read_strings(name, item) {
+            match Pattern::new(&pattern) {
+                Ok(pattern) => patterns.push(pattern),
+                Err(err) => self.error(
+                    span,
+                    format!("invalid glob \"{}\" in \"{}\": {}", pattern, name, err),
+                ),
+            }
+        }
+        patterns
+    }
+
+    fn read_per_file_ignores(&mut self, item: &Item) -> Vec<(Pattern, Vec<String>)> {
+        let Some(table) = item.as_table_like() else {
+            self.error(
+                item.span(),
+                "\"per-file-ignores\" should be a table of glob patterns to lists of codes"
+                    .to_string(),
+            );
+            return Vec::new();
+        };
+        let mut per_file_ignores = Vec::new();
+        for (pattern, codes) in table.iter() {
+            let compiled = match Pattern::new(pattern) {
+                Ok(compiled) => compiled,
+                Err(err) => {
+                    self.error(
+                        codes.span(),
+                        format!(
+                            "invalid glob \"{}\" in \"per-file-ignores\": {}",
+                            pattern, err
+                        ),
+                    );
+                    continue;
+                }
+            };
+            let codes = self.read_codes("per-file-ignores", codes);
+            per_file_ignores.push((compiled, codes));
+        }
+        per_file_ignores
+    }
+}
+
+////////// Tests
+
+#[test]
+pub fn test_parse_vipyrdocs_toml() {
+    let contents = r#"
+convention = "numpy"
+select = ["DCO02", "D050"]
+ignore = ["DCO025"]
+exclude = ["legacy", "*_pb2.py"]
+test-file-patterns = ["*_test.py"]
+
+[per-file-ignores]
+"scripts/*" = ["DCO010"]
+"#;
+    let config = Config::parse(contents, Path::new("vipyrdocs.toml")).unwrap();
+    assert_eq!(config.convention, Some(DocstringStyle::Numpy));
+    assert_eq!(
+        config.select,
+        Some(vec!["D02".to_string(), "D050".to_string()])
+    );
+    assert_eq!(config.ignore, vec!["D025".to_string()]);
+    assert_eq!(config.exclude, to_patterns(&["legacy", "*_pb2.py"]));
+    assert_eq!(config.test_file_patterns, to_patterns(&["*_test.py"]));
+    assert_eq!(
+        config.per_file_ignores,
+        vec![(Pattern::new("scripts/*").unwrap(), vec!["D010".to_string()])]
+    );
+}
+
+#[test]
+pub fn test_parse_pyproject_toml() {
+    let contents = r#"
+[project]
+name = "example"
+
+[tool.vipyrdocs]
+ignore = ["DCO010"]
+"#;
+    let config = Config::parse(contents, Path::new("pyproject.toml")).unwrap();
+    assert_eq!(config.ignore, vec!["D010".to_string()]);
+    assert_eq!(config.select, None);
+
+    let config = Config::parse(
+        "[project]\nname = \"example\"\n",
+        Path::new("pyproject.toml"),
+    );
+    assert_eq!(config.unwrap().ignore, Vec::<String>::new());
+}
+
+#[test]
+pub fn test_parse_reports_bad_keys_with_line_numbers() {
+    let contents = r#"[project]
+name = "example"
+
+[tool.vipyrdocs]
+select = ["DCO010"]
+ignored = ["DCO020"]
+exclude = "legacy"
+ignore = ["DCO999", 10]
+"#;
+    let errors = Config::parse(contents, Path::new("pyproject.toml")).unwrap_err();
+    let rendered: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+    assert_eq!(rendered.len(), 4, "{:#?}", rendered);
+    assert!(rendered[0].starts_with("pyproject.toml:6: unknown key \"ignored\""));
+    assert_eq!(
+        rendered[1],
+        "pyproject.toml:7: \"exclude\" should be a list of strings"
+    );
+    assert_eq!(
+        rendered[2],
+        "pyproject.toml:8: \"ignore\" should only contain strings"
+    );
+    assert_eq!(
+        rendered[3],
+        "pyproject.toml:8: unknown rule code or prefix \"DCO999\" in \"ignore\""
+    );
+}
+
+#[test]
+pub fn test_parse_reports_unknown_convention() {
+    let errors =
+        Config::parse("convention = \"epytext\"\n", Path::new("vipyrdocs.toml")).unwrap_err();
+    assert_eq!(
+        errors[0].to_string(),
+        "vipyrdocs.toml:1: \"convention\" should be one of: google, numpy, sphinx"
+    );
+}
+
+#[test]
+pub fn test_parse_reports_invalid_toml() {
+    let errors = Config::parse("select = [\n", Path::new("vipyrdocs.toml")).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].message.starts_with("invalid TOML"));
+}
+
+#[test]
+pub fn test_is_enabled() {
+    let config = Config {
+        select: Some(vec!["D02".to_string(), "D050".to_string()]),
+        ignore: vec!["D025".to_string(), "D0".to_string()],
+        ..Config::default()
+    };
+    assert!(config.is_enabled("D020"));
+    assert!(!config.is_enabled("D025"));
+    assert!(config.is_enabled("D050"));
+    assert!(!config.is_enabled("D051"));
+    assert!(!config.is_enabled("D010"));
+    assert!(Config::default().is_enabled("D010"));
+
+    let config = Config {
+        select: Some(vec!["ALL".to_string()]),
+        extend_select: vec!["D002".to_string()],
+        ..Config::default()
+    };
+    assert!(config.is_enabled("D002"));
+    assert!(!config.is_enabled("D003"));
+    assert!(!Config::default().is_enabled("D002"));
+}
+
+#[test]
+pub fn test_path_matching() {
+    let config = Config {
+        exclude: to_patterns(&["legacy", "*_pb2.py"]),
+        per_file_ignores: vec![(Pattern::new("scripts/*").unwrap(), vec!["D010".to_string()])],
+        ..Config::default()
+    };
+    assert!(config.is_excluded("src/legacy"));
+    assert!(config.is_excluded("./proto/service_pb2.py"));
+    assert!(!config.is_excluded("src/module.py"));
+    assert!(config.is_ignored_for_file("scripts/run.py", "D010"));
+    assert!(!config.is_ignored_for_file("scripts/run.py", "D020"));
+    assert!(!config.is_ignored_for_file("src/run.py", "D010"));
+    assert!(config.is_test_file("tests/test_module.py"));
+    assert!(config.is_test_file("conftest.py"));
+    assert!(!config.is_test_file("src/module.py"));
+    assert!(!config.is_test_file("test_data/module.py"));
+}
+
+#[test]
+pub fn test_include_and_exclude() {
+    let config = Config::default();
+    assert!(config.is_excluded("./.venv"));
+    assert!(config.is_excluded("/home/user/project/build"));
+    assert!(!config.is_excluded("src/module.py"));
+    assert!(config.is_included("src/module.py"));
+    assert!(!config.is_included("src/module.pyi"));
+
+    let contents = r#"
+exclude = ["legacy"]
+extend-exclude = ["*_pb2.py"]
+extend-include = ["*.pyi"]
+"#;
+    let config = Config::parse(contents, Path::new("vipyrdocs.toml")).unwrap();
+    assert!(!config.is_excluded("build"));
+    assert!(config.is_excluded("legacy"));
+    assert!(config.is_excluded("proto/service_pb2.py"));
+    assert!(config.is_included("src/module.pyi"));
+}
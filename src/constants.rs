@@ -1,6 +1,194 @@
 pub const ERROR_CODE_PREFIX: &str = "D";
 pub const MORE_INFO_BASE: &str = " (more info: https://example.com/";
 
+/// Turns a user supplied rule code or prefix into the form the rules report,
+/// so both the documented `DCO010` and the reported `D010` spellings work.
+pub fn normalize_code(code: &str) -> String {
+    let code = code.trim().to_uppercase();
+    match code.strip_prefix("DCO") {
+        Some(rest) => format!("{}{}", ERROR_CODE_PREFIX, rest),
+        None => code,
+    }
+}
+
+/// Whether a user supplied `code` names rules of this tool, `DCO020` or
+/// `D020`, rather than of another tool sharing the `# noqa` comment, e.g.
+/// `F401`.
+pub fn is_own_code(code: &str) -> bool {
+    let code = code.trim().to_uppercase();
+    code.starts_with("DCO") || code.starts_with(format!("{}0", ERROR_CODE_PREFIX).as_str())
+}
+
+/// Link to the documentation of a rule, e.g. `https://example.com/d010`.
+pub fn more_info_url(code: &str) -> String {
+    format!(
+        "{}{}",
+        MORE_INFO_BASE.trim_start_matches(" (more info: "),
+        code.to_lowercase()
+    )
+}
+
+/// Every rule code with a one line description of what it checks.
+pub fn all_rules() -> Vec<(String, &'static str)> {
+    vec![
+        (
+            docstr_missing_code(),
+            "docstring missing on a function/ method/ class",
+        ),
+        (
+            args_section_not_in_docstr_code(),
+            "function/ method with arguments does not have an arguments section",
+        ),
+        (
+            args_section_in_docstr_code(),
+            "function/ method without arguments has an arguments section",
+        ),
+        (
+            mult_args_sections_in_docstr_code(),
+            "docstring has multiple arguments sections",
+        ),
+        (
+            arg_not_in_docstr_code(),
+            "argument not described in the docstring",
+        ),
+        (
+            arg_in_docstr_code(),
+            "docstring describes an argument the function/ method does not have",
+        ),
+        (
+            duplicate_arg_in_docstr_code(),
+            "argument described in the docstring multiple times",
+        ),
+        (
+            returns_section_not_in_docstr_code(),
+            "function/ method that returns a value does not have a returns section",
+        ),
+        (
+            returns_section_in_docstr_code(),
+            "function/ method that does not return a value has a returns section",
+        ),
+        (
+            mult_returns_sections_in_docstr_code(),
+            "docstring has multiple returns sections",
+        ),
+        (
+            yields_section_not_in_docstr_code(),
+            "function/ method that yields a value does not have a yields section",
+        ),
+        (
+            yields_section_in_docstr_code(),
+            "function/ method that does not yield a value has a yields section",
+        ),
+        (
+            mult_yields_sections_in_docstr_code(),
+            "docstring has multiple yields sections",
+        ),
+        (
+            raises_section_not_in_docstr_code(),
+            "function/ method that raises exceptions does not have a raises section",
+        ),
+        (
+            raises_section_in_docstr_code(),
+            "function/ method that raises no exceptions has a raises section",
+        ),
+        (
+            mult_raises_sections_in_docstr_code(),
+            "docstring has multiple raises sections",
+        ),
+        (
+            exc_not_in_docstr_code(),
+            "raised exception not described in the docstring",
+        ),
+        (
+            exc_in_docstr_code(),
+            "docstring describes an exception that is not raised",
+        ),
+        (
+            re_raise_no_exc_in_docstr_code(),
+            "function/ method that re-raises has an empty raises section",
+        ),
+        (
+            duplicate_exc_code(),
+            "exception described in the docstring multiple times",
+        ),
+        (
+            attrs_section_not_in_docstr_code(),
+            "class with public attributes does not have an attributes section",
+        ),
+        (
+            attrs_section_in_docstr_code(),
+            "class without public attributes has an attributes section",
+        ),
+        (
+            mult_attrs_sections_in_docstr_code(),
+            "docstring has multiple attributes sections",
+        ),
+        (
+            attr_not_in_docstr_code(),
+            "public attribute not described in the docstring",
+        ),
+        (
+            attr_in_docstr_code(),
+            "docstring describes an attribute the class does not have",
+        ),
+        (
+            duplicate_attr_in_docstr_code(),
+            "attribute described in the docstring multiple times",
+        ),
+        (parse_error_code(), "file could not be parsed"),
+        (
+            read_error_code(),
+            "file could not be read, decoded or checked",
+        ),
+        (unused_noqa_code(), "noqa directive that suppresses nothing"),
+        (
+            unknown_code_in_noqa_code(),
+            "noqa directive names an unknown rule code",
+        ),
+    ]
+}
+
+/// Rules that only run when selected explicitly, `--select ALL` or the
+/// default selection does not turn them on.
+pub fn opt_in_rules() -> Vec<String> {
+    vec![unused_noqa_code(), unknown_code_in_noqa_code()]
+}
+
+pub fn parse_error_code() -> String {
+    format!("{}001", ERROR_CODE_PREFIX)
+}
+pub fn parse_error_msg(_error: &str) -> String {
+    format!(
+        "{} file could not be parsed: {}{}{}",
+        parse_error_code(),
+        _error,
+        MORE_INFO_BASE,
+        parse_error_code().to_lowercase()
+    )
+}
+
+pub fn read_error_code() -> String {
+    format!("{}004", ERROR_CODE_PREFIX)
+}
+pub fn read_error_msg(_error: &str) -> String {
+    format!(
+        "{} file could not be read: {}{}{}",
+        read_error_code(),
+        _error,
+        MORE_INFO_BASE,
+        read_error_code().to_lowercase()
+    )
+}
+pub fn check_error_msg(_error: &str) -> String {
+    format!(
+        "{} file could not be checked: {}{}{}",
+        read_error_code(),
+        _error,
+        MORE_INFO_BASE,
+        read_error_code().to_lowercase()
+    )
+}
+
 pub fn docstr_missing_code() -> String {
     format!("{}010", ERROR_CODE_PREFIX)
 }
@@ -248,3 +436,109 @@ pub fn duplicate_exc_msg(_raise: &str) -> String {
         duplicate_exc_code().to_lowercase()
     )
 }
+
+pub fn attrs_section_not_in_docstr_code() -> String {
+    format!("{}060", ERROR_CODE_PREFIX)
+}
+pub fn attrs_section_not_in_docstr_msg() -> String {
+    format!(
+        "{} a class with public attributes should have the attributes section in the docstring{}{}",
+        attrs_section_not_in_docstr_code(),
+        MORE_INFO_BASE,
+        attrs_section_not_in_docstr_code().to_lowercase()
+    )
+}
+
+pub fn attrs_section_in_docstr_code() -> String {
+    format!("{}061", ERROR_CODE_PREFIX)
+}
+pub fn attrs_section_in_docstr_msg() -> String {
+    format!(
+        "{} a class without public attributes should not have the attributes section in the docstring{}{}",
+        attrs_section_in_docstr_code(),
+        MORE_INFO_BASE,
+        attrs_section_in_docstr_code().to_lowercase()
+    )
+}
+
+pub fn mult_attrs_sections_in_docstr_code() -> String {
+    format!("{}062", ERROR_CODE_PREFIX)
+}
+pub fn mult_attrs_sections_in_docstr_msg(found: &str) -> String {
+    format!(
+        "{} a docstring should only contain a single attributes section, found {}{}{}",
+        mult_attrs_sections_in_docstr_code(),
+        found,
+        MORE_INFO_BASE,
+        mult_attrs_sections_in_docstr_code().to_lowercase()
+    )
+}
+
+pub fn attr_not_in_docstr_code() -> String {
+    format!("{}063", ERROR_CODE_PREFIX)
+}
+pub fn attr_not_in_docstr_msg(_attr: &str) -> String {
+    format!(
+        "{} \"{}\" attribute/ property should be described in the docstring{}{}",
+        attr_not_in_docstr_code(),
+        _attr,
+        MORE_INFO_BASE,
+        attr_not_in_docstr_code().to_lowercase()
+    )
+}
+
+pub fn attr_in_docstr_code() -> String {
+    format!("{}064", ERROR_CODE_PREFIX)
+}
+pub fn attr_in_docstr_msg(_attr: &str) -> String {
+    format!(
+        "{} \"{}\" attribute should not be described in the docstring{}{}",
+        attr_in_docstr_code(),
+        _attr,
+        MORE_INFO_BASE,
+        attr_in_docstr_code().to_lowercase()
+    )
+}
+
+pub fn duplicate_attr_in_docstr_code() -> String {
+    format!("{}065", ERROR_CODE_PREFIX)
+}
+pub fn duplicate_attr_msg(_attr: &str) -> String {
+    format!(
+        "{} \"{}\" attribute documented multiple times{}{}",
+        duplicate_attr_in_docstr_code(),
+        _attr,
+        MORE_INFO_BASE,
+        duplicate_attr_in_docstr_code().to_lowercase()
+    )
+}
+
+pub fn unused_noqa_code() -> String {
+    format!("{}002", ERROR_CODE_PREFIX)
+}
+pub fn unused_noqa_msg(_codes: Option<&str>) -> String {
+    let target = match _codes {
+        Some(codes) => format!(" for {}", codes),
+        None => String::new(),
+    };
+    format!(
+        "{} unused noqa directive{}{}{}",
+        unused_noqa_code(),
+        target,
+        MORE_INFO_BASE,
+        unused_noqa_code().to_lowercase()
+    )
+}
+
+pub fn unknown_code_in_noqa_code() -> String {
+    format!("{}003", ERROR_CODE_PREFIX)
+}
+pub fn unknown_code_in_noqa_msg(_code: &str) -> String {
+    format!(
+        "{} \"{}\" in the noqa directive is not a known rule code{}{}",
+        unknown_code_in_noqa_code(),
+        _code,
+        MORE_INFO_BASE,
+        unknown_code_in_noqa_code().to_lowercase()
+    )
+}
@@ -0,0 +1,179 @@
+use pyo3::prelude::*;
+use std::fmt;
+
+/// How serious a reported problem is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// Whether a fix can be applied without asking, an unsafe fix can change
+/// what the docstring says and needs `--unsafe-fixes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Applicability {
+    Safe,
+    Unsafe,
+}
+
+/// Replaces the source between the byte offsets `start` and `end` with
+/// `content`, an insertion when both are the same.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Edit {
+    pub start: usize,
+    pub end: usize,
+    pub content: String,
+}
+
+impl Edit {
+    pub fn insertion(offset: usize, content: String) -> Self {
+        Edit {
+            start: offset,
+            end: offset,
+            content,
+        }
+    }
+
+    pub fn deletion(start: usize, end: usize) -> Self {
+        Edit {
+            start,
+            end,
+            content: String::new(),
+        }
+    }
+}
+
+/// The edits that resolve a diagnostic, applied together or not at all.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fix {
+    pub edits: Vec<Edit>,
+    pub applicability: Applicability,
+}
+
+impl Fix {
+    pub fn safe(edits: Vec<Edit>) -> Self {
+        Fix {
+            edits,
+            applicability: Applicability::Safe,
+        }
+    }
+
+    pub fn unsafe_edits(edits: Vec<Edit>) -> Self {
+        Fix {
+            edits,
+            applicability: Applicability::Unsafe,
+        }
+    }
+}
+
+/// A single problem found by a rule. Lines are 1-based, columns 0-based;
+/// the end position points just past the offending code.
+///
+/// Diagnostics order by file, then position, then code, so sorting a list
+/// of them gives a stable report.
+#[pyclass(module = "ruff_docstrings_complete._core")]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Diagnostic {
+    #[pyo3(get)]
+    pub file: Option<String>,
+    #[pyo3(get)]
+    pub line: usize,
+    #[pyo3(get)]
+    pub column: usize,
+    #[pyo3(get)]
+    pub end_line: usize,
+    #[pyo3(get)]
+    pub end_column: usize,
+    #[pyo3(get)]
+    pub code: String,
+    #[pyo3(get)]
+    pub message: String,
+    pub severity: Severity,
+    #[pyo3(get)]
+    pub symbol: String,
+    /// How `--fix` resolves the problem, if it can.
+    pub fix: Option<Fix>,
+    /// The byte range of the function the problem was found in, to find it
+    /// again for a fix.
+    pub definition: Option<(usize, usize)>,
+}
+
+impl Diagnostic {
+    pub fn new(
+        code: String,
+        message: String,
+        symbol: &str,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Self {
+        Diagnostic {
+            file: None,
+            line: start.0,
+            column: start.1,
+            end_line: end.0,
+            end_column: end.1,
+            code,
+            message,
+            severity: Severity::Error,
+            symbol: symbol.to_string(),
+            fix: None,
+            definition: None,
+        }
+    }
+
+    pub fn with_file(mut self, file: Option<&str>) -> Self {
+        self.file = file.map(|file| file.to_string());
+        self
+    }
+}
+
+#[pymethods]
+impl Diagnostic {
+    /// `"error"` or `"warning"`.
+    #[getter(severity)]
+    fn py_severity(&self) -> &'static str {
+        self.severity.as_str()
+    }
+
+    /// Whether `--fix` can resolve the problem.
+    #[getter]
+    fn fixable(&self) -> bool {
+        self.fix.is_some()
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        let file = self
+            .file
+            .as_ref()
+            .map_or("None".to_string(), |file| format!("'{}'", file));
+        format!(
+            "Diagnostic(code='{}', line={}, column={}, symbol='{}', file={})",
+            self.code, self.line, self.column, self.symbol, file
+        )
+    }
+}
+
+/// Renders the diagnostic as `line:column message`, the format the rules
+/// have always reported in.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{} {}", self.line, self.column, self.message)
+    }
+}
@@ -0,0 +1,236 @@
+use crate::constants::read_error_code;
+use crate::diagnostic::Diagnostic;
+use crate::rule_engine::{definition_of, file_definition_ranges};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+lazy_static::lazy_static! {
+    // `@@ -12,3 +14,5 @@`, a missing count means one line
+    static ref HUNK_PATTERN: Regex =
+        Regex::new(r"^@@ -\d+(?:,\d+)? \+(?P<start>\d+)(?:,(?P<count>\d+))? @@").unwrap();
+}
+
+/// The lines changed since a git revision, by canonical file path. Ranges
+/// are 1-based and inclusive.
+#[derive(Debug, Default)]
+pub struct ChangedLines {
+    files: HashMap<PathBuf, Vec<(usize, usize)>>,
+}
+
+impl ChangedLines {
+    /// Diffs the working tree of the repositories containing `paths`
+    /// against `base`. Untracked files count as changed entirely.
+    pub fn since(base: &str, paths: &[PathBuf]) -> Result<ChangedLines, String> {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for path in paths {
+            let root = repository_root(path)?;
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        let mut changed = ChangedLines::default();
+        for root in roots {
+            changed.add_repository(base, &root)?;
+        }
+        Ok(changed)
+    }
+
+    fn add_repository(&mut self, base: &str, root: &Path) -> Result<(), String> {
+        let diff = git(
+            root,
+            &[
+                "-c",
+                "core.quotePath=false",
+                "diff",
+                // whatever `diff.noprefix` or `diff.mnemonicPrefix` say
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-relative",
+                base,
+                "--",
+            ],
+        )?;
+        for (file, ranges) in parse_diff(&diff) {
+            self.insert(&root.join(file), ranges);
+        }
+
+        let untracked = git(
+            root,
+            &[
+                "-c",
+                "core.quotePath=false",
+                "ls-files",
+                "--others",
+                "--exclude-standard",
+            ],
+        )?;
+        for file in untracked.lines() {
+            self.insert(&root.join(file), vec![(1, usize::MAX)]);
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, file: &Path, ranges: Vec<(usize, usize)>) {
+        self.files
+            .entry(canonical(file))
+            .or_default()
+            .extend(ranges);
+    }
+
+    pub fn contains_file(&self, file: &str) -> bool {
+        self.files.contains_key(&canonical(Path::new(file)))
+    }
+
+    fn overlaps(&self, file: &str, start: usize, end: usize) -> bool {
+        self.files
+            .get(&canonical(Path::new(file)))
+            .is_some_and(|ranges| {
+                ranges
+                    .iter()
+                    .any(|(first, last)| *first <= end && start <= *last)
+            })
+    }
+
+    /// Keeps the diagnostics whose function or class overlaps a changed
+    /// line, or whose own lines do for module level problems.
+    pub fn retain_changed(&self, file: &str, output: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        if output.is_empty() {
+            return output;
+        }
+        let definitions = file_definition_ranges(file);
+
+        output
+            .into_iter()
+            .filter(|diagnostic| {
+                // a file that could not be read was not checked at all
+                if diagnostic.code == read_error_code() {
+                    return true;
+                }
+                let (start, end) = match definition_of(&definitions, diagnostic) {
+                    Some((_, start, end)) => (*start, *end),
+                    None => (diagnostic.line, diagnostic.end_line),
+                };
+                self.overlaps(file, start, end)
+            })
+            .collect()
+    }
+}
+
+/// The top level directory of the repository containing `path`.
+fn repository_root(path: &Path) -> Result<PathBuf, String> {
+    let dir = if path.is_dir() {
+        path
+    } else {
+        path.parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+    };
+    Ok(PathBuf::from(
+        git(dir, &["rev-parse", "--show-toplevel"])?.trim(),
+    ))
+}
+
+fn canonical(file: &Path) -> PathBuf {
+    file.canonicalize().unwrap_or_else(|_| file.to_path_buf())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| format!("could not run git: {}", err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.iter()
+                .find(|arg| !arg.starts_with('-') && !arg.contains('='))
+                .unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// The new-side line ranges of every file in a `--unified=0` diff. A hunk
+/// that only deletes lines touches the lines around the deletion.
+fn parse_diff(diff: &str) -> Vec<(String, Vec<(usize, usize)>)> {
+    let mut files: Vec<(String, Vec<(usize, usize)>)> = Vec::new();
+    for line in diff.lines() {
+        if let Some(file) = line.strip_prefix("+++ ") {
+            // git ends a path with a space in it with a tab
+            let file = file.strip_suffix('\t').unwrap_or(file);
+            if let Some(file) = file.strip_prefix("b/") {
+                files.push((file.to_string(), Vec::new()));
+            } else {
+                // `+++ /dev/null`, the file was deleted
+                files.push((String::new(), Vec::new()));
+            }
+            continue;
+        }
+        let Some(captures) = HUNK_PATTERN.captures(line) else {
+            continue;
+        };
+        let Some((_, ranges)) = files.last_mut() else {
+            continue;
+        };
+        let start: usize = captures["start"].parse().unwrap_or(0);
+        let count: usize = captures
+            .name("count")
+            .map_or(1, |count| count.as_str().parse().unwrap_or(1));
+        if count == 0 {
+            ranges.push((start.max(1), start + 1));
+        } else {
+            ranges.push((start, start + count - 1));
+        }
+    }
+    files.retain(|(file, _)| !file.is_empty());
+    files
+}
+
+////////// Tests
+
+#[test]
+pub fn test_parse_diff() {
+    let diff = r#"diff --git a/src/module.py b/src/module.py
+index 1111111..2222222 100644
+--- a/src/module.py
++++ b/src/module.py
+@@ -3 +3 @@ def function_1():
+-    pass
++    return 1
+@@ -10,2 +10,0 @@ def function_2():
+@@ -20,0 +19,3 @@ def function_3():
+diff --git a/old.py b/old.py
+deleted file mode 100644
+--- a/old.py
++++ /dev/null
+@@ -1,2 +0,0 @@
+diff --git a/new.py b/new.py
+new file mode 100644
+--- /dev/null
++++ b/new.py
+@@ -0,0 +1,2 @@
+diff --git a/my module.py b/my module.py
+--- a/my module.py	
++++ b/my module.py	
+@@ -1 +1 @@
+"#;
+    assert_eq!(
+        parse_diff(diff),
+        vec![
+            (
+                "src/module.py".to_string(),
+                vec![(3, 3), (10, 11), (19, 21)]
+            ),
+            ("new.py".to_string(), vec![(1, 2)]),
+            ("my module.py".to_string(), vec![(1, 1)]),
+        ]
+    );
+}
@@ -1,11 +1,11 @@
 use pyo3::prelude::*;
 
 use regex::Regex;
-use rustpython_ast::text_size::TextRange;
+use rustpython_ast::text_size::{TextRange, TextSize};
 use rustpython_ast::ExprConstant;
-use rustpython_ast::TextSize;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::ops::Range;
 
 lazy_static::lazy_static! {
     static ref _SECTION_NAMES: HashMap<&'static str, HashSet<&'static str>> = {
@@ -28,6 +28,87 @@ lazy_static::lazy_static! {
     static ref _SUB_SECTION_PATTERN : regex::Regex = regex::Regex::new(r"\s*(\w+)( \(.*\))?:").unwrap();
 }
 
+lazy_static::lazy_static! {
+    // the dashes under a NumPy style section name
+    static ref NUMPY_UNDERLINE_PATTERN: Regex = Regex::new(r"^\s*-{3,}\s*$").unwrap();
+}
+
+lazy_static::lazy_static! {
+    // `name`, `name : type`, `*args` or `name_1, name_2 : type`
+    static ref NUMPY_ENTRY_PATTERN: Regex =
+        Regex::new(r"^\s*(\*{0,2}[\w.]+(?:\s*,\s*\*{0,2}[\w.]+)*)\s*(?::.*)?$").unwrap();
+}
+
+lazy_static::lazy_static! {
+    // `:param x:`, `:param int x:`, `:raises ValueError:` or `:returns:`
+    static ref SPHINX_FIELD_PATTERN: Regex = Regex::new(r"^\s*:(\w+)([^:]*):").unwrap();
+}
+
+lazy_static::lazy_static! {
+    // the reST fields of each section, named after the matching Google section
+    static ref _SPHINX_FIELD_NAMES: HashMap<&'static str, HashSet<&'static str>> = {
+        let mut map = HashMap::new();
+        map.insert(
+            "args",
+            HashSet::from(["param", "parameter", "arg", "argument", "key", "keyword"]),
+        );
+        map.insert("attrs", HashSet::from(["ivar", "cvar", "var"]));
+        map.insert("returns", HashSet::from(["return", "returns"]));
+        map.insert("yields", HashSet::from(["yield", "yields"]));
+        map.insert("raises", HashSet::from(["raises", "raise", "except", "exception"]));
+        map
+    };
+}
+
+/// The docstring conventions [`parse`] understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocstringStyle {
+    /// `Args:` headers with indented `name: description` entries
+    Google,
+    /// `Parameters` headers underlined with dashes
+    Numpy,
+    /// reST fields such as `:param x:` and `:raises ValueError:`
+    Sphinx,
+}
+
+impl DocstringStyle {
+    pub const NAMES: [&'static str; 3] = ["google", "numpy", "sphinx"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "google" => Some(DocstringStyle::Google),
+            "numpy" => Some(DocstringStyle::Numpy),
+            "sphinx" => Some(DocstringStyle::Sphinx),
+            _ => None,
+        }
+    }
+}
+
+/// Guesses the convention of a docstring from its lines: any reST field
+/// makes it Sphinx, any underlined section name NumPy, anything else Google.
+pub fn detect_style(lines: &[String]) -> DocstringStyle {
+    let is_sphinx_field = |line: &String| {
+        SPHINX_FIELD_PATTERN
+            .captures(line)
+            .and_then(|caps| caps.get(1))
+            .is_some_and(|field| {
+                let field = field.as_str().to_lowercase();
+                _SPHINX_FIELD_NAMES
+                    .values()
+                    .any(|names| names.contains(field.as_str()))
+            })
+    };
+    if lines.iter().any(is_sphinx_field) {
+        return DocstringStyle::Sphinx;
+    }
+    let is_numpy_header =
+        |pair: &[String]| !pair[0].trim().is_empty() && NUMPY_UNDERLINE_PATTERN.is_match(&pair[1]);
+    if lines.windows(2).any(is_numpy_header) {
+        return DocstringStyle::Numpy;
+    }
+    DocstringStyle::Google
+}
+
 #[pyclass]
 #[derive(Debug, PartialEq)]
 pub struct _Section {
@@ -40,7 +121,7 @@ impl _Section {
     #[new]
     #[pyo3(signature = (name=None, subs=None))]
     fn new(name: Option<String>, subs: Option<Vec<String>>) -> Self {
-        let subsections = subs.unwrap_or_else(|| vec![]);
+        let subsections = subs.unwrap_or_default();
         _Section {
             name,
             subs: subsections,
@@ -65,22 +146,49 @@ impl _Section {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "ruff_docstrings_complete._core.docstring")]
 #[derive(Debug, PartialEq, Clone)]
 pub struct Docstring {
+    #[pyo3(get)]
     args: Option<Vec<String>>,
+    #[pyo3(get)]
     args_sections: Option<Vec<String>>,
+    #[pyo3(get)]
     attrs: Option<Vec<String>>,
+    #[pyo3(get)]
     attrs_sections: Option<Vec<String>>,
+    #[pyo3(get)]
     returns_sections: Option<Vec<String>>,
+    #[pyo3(get)]
     yields_sections: Option<Vec<String>>,
+    #[pyo3(get)]
     raises: Option<Vec<String>>,
+    #[pyo3(get)]
     raises_sections: Option<Vec<String>>,
+    /// Every section with its entries, descriptions and lines.
+    #[pyo3(get)]
+    sections: Vec<Section>,
     range: TextRange,
+    /// The style the sections were parsed in.
+    style: DocstringStyle,
 }
 
+#[pymethods]
 impl Docstring {
-    fn new(
+    /// Built from the names only, to compare with what [`parse`] returns.
+    #[new]
+    #[pyo3(signature = (
+        args=None,
+        args_sections=None,
+        attrs=None,
+        attrs_sections=None,
+        returns_sections=None,
+        yields_sections=None,
+        raises=None,
+        raises_sections=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
         args: Option<Vec<String>>,
         args_sections: Option<Vec<String>>,
         attrs: Option<Vec<String>>,
@@ -89,9 +197,8 @@ impl Docstring {
         yields_sections: Option<Vec<String>>,
         raises: Option<Vec<String>>,
         raises_sections: Option<Vec<String>>,
-        range: TextRange,
     ) -> Self {
-        Docstring {
+        Docstring::new(
             args,
             args_sections,
             attrs,
@@ -100,8 +207,21 @@ impl Docstring {
             yields_sections,
             raises,
             raises_sections,
-            range,
-        }
+            Vec::new(),
+            TextRange::default(),
+        )
+    }
+
+    /// The entries of the returns sections, `None` without one.
+    #[getter]
+    fn returns(&self) -> Option<Vec<Entry>> {
+        self.entries_of("returns")
+    }
+
+    /// The entries of the yields sections, `None` without one.
+    #[getter]
+    fn yields(&self) -> Option<Vec<Entry>> {
+        self.entries_of("yields")
     }
 
     fn __eq__(&self, other: &Docstring) -> PyResult<bool> {
@@ -134,6 +254,50 @@ impl Docstring {
         self.range,
     )
     }
+}
+
+impl Docstring {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        args: Option<Vec<String>>,
+        args_sections: Option<Vec<String>>,
+        attrs: Option<Vec<String>>,
+        attrs_sections: Option<Vec<String>>,
+        returns_sections: Option<Vec<String>>,
+        yields_sections: Option<Vec<String>>,
+        raises: Option<Vec<String>>,
+        raises_sections: Option<Vec<String>>,
+        sections: Vec<Section>,
+        range: TextRange,
+    ) -> Self {
+        Docstring {
+            args,
+            args_sections,
+            attrs,
+            attrs_sections,
+            returns_sections,
+            yields_sections,
+            raises,
+            raises_sections,
+            sections,
+            range,
+            style: DocstringStyle::Google,
+        }
+    }
+
+    /// The entries of every section of `kind`, e.g. `"returns"`.
+    fn entries_of(&self, kind: &str) -> Option<Vec<Entry>> {
+        let entries: Vec<Entry> = self
+            .sections
+            .iter()
+            .filter(|section| section.kind.as_deref() == Some(kind))
+            .flat_map(|section| section.entries.iter().cloned())
+            .collect();
+        self.sections
+            .iter()
+            .any(|section| section.kind.as_deref() == Some(kind))
+            .then_some(entries)
+    }
 
     pub fn is_empty(&self) -> bool {
         if self.args.is_some()
@@ -242,44 +406,343 @@ impl Docstring {
         }
         self.args.clone().unwrap()
     }
+    pub fn has_attrs_sections(&self) -> bool {
+        if self.attrs_sections.is_none() {
+            return false;
+        }
+        if self.attrs_sections.clone().unwrap().is_empty() {
+            return false;
+        }
+        true
+    }
+    pub fn get_attrs_sections(&self) -> Vec<String> {
+        if self.attrs_sections.is_none() {
+            return Vec::<String>::new();
+        }
+        self.attrs_sections.clone().unwrap()
+    }
+    pub fn get_attrs(&self) -> Vec<String> {
+        if self.attrs.is_none() {
+            return Vec::<String>::new();
+        }
+        self.attrs.clone().unwrap()
+    }
     pub fn get_range(&self) -> TextRange {
         self.range
     }
+    pub fn get_style(&self) -> DocstringStyle {
+        self.style
+    }
+}
+
+/// Where a section is in the docstring text its lines come from, as byte
+/// ranges, so a fix can rewrite its entries and leave the rest alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionSpan {
+    pub name: Option<String>,
+    pub range: Range<usize>,
+    /// Where the entries start, after the section name.
+    pub body: usize,
+    pub entries: Vec<EntrySpan>,
+}
+
+impl SectionSpan {
+    /// Whether this is one of the `kind` sections, e.g. `"args"`.
+    pub fn is(&self, kind: &str) -> bool {
+        self.name
+            .as_ref()
+            .is_some_and(|name| _SECTION_NAMES[kind].contains(name.to_lowercase().as_str()))
+    }
+}
+
+/// An entry of a section with its description lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntrySpan {
+    /// The names the entry documents, none for a line without one.
+    pub names: Vec<String>,
+    pub range: Range<usize>,
+    /// Where the description starts, after the name.
+    pub description: usize,
+}
+
+/// The offset of every line in the text `lines` were split from on `\n`,
+/// followed by the length of that text plus one.
+fn line_starts(lines: &[String]) -> Vec<usize> {
+    let mut starts = vec![0];
+    for line in lines {
+        starts.push(starts[starts.len() - 1] + line.len() + 1);
+    }
+    starts
+}
+
+/// The end of line `index` with its newline, if it has one.
+fn line_end(starts: &[usize], index: usize) -> usize {
+    starts[index + 1].min(starts[starts.len() - 1] - 1)
+}
+
+/// A section of a parsed docstring, as Python sees it. Line offsets count
+/// from the first line of the docstring.
+#[pyclass(module = "ruff_docstrings_complete._core.docstring", eq, get_all)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// The name as written, `None` for the summary and description.
+    name: Option<String>,
+    /// `"args"`, `"attrs"`, `"returns"`, `"yields"` or `"raises"`, `None`
+    /// for any other section.
+    kind: Option<String>,
+    /// The text before the entries, the whole text without a name.
+    description: String,
+    entries: Vec<Entry>,
+    line_offset: usize,
+    end_line_offset: usize,
+}
+
+#[pymethods]
+impl Section {
+    fn __repr__(&self) -> String {
+        format!(
+            "Section(name={}, kind={}, entries={}, line_offset={}, end_line_offset={})",
+            py_repr(&self.name),
+            py_repr(&self.kind),
+            self.entries.len(),
+            self.line_offset,
+            self.end_line_offset
+        )
+    }
+}
+
+/// An entry of a [`Section`], such as an argument with its type annotation.
+#[pyclass(module = "ruff_docstrings_complete._core.docstring", eq, get_all)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// More than one for NumPy style `x1, x2 : int`, none for a return
+    /// value without a name.
+    names: Vec<String>,
+    annotation: Option<String>,
+    description: String,
+    line_offset: usize,
+    end_line_offset: usize,
+}
+
+#[pymethods]
+impl Entry {
+    /// The first of the names.
+    #[getter]
+    fn name(&self) -> Option<String> {
+        self.names.first().cloned()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Entry(names={:?}, annotation={}, description={:?}, line_offset={}, end_line_offset={})",
+            self.names,
+            py_repr(&self.annotation),
+            self.description,
+            self.line_offset,
+            self.end_line_offset
+        )
+    }
+}
+
+fn py_repr(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("{:?}", value),
+        None => "None".to_string(),
+    }
+}
+
+/// The first and last line of `range` in `text`, leaving out trailing blank
+/// lines.
+fn line_offsets(text: &str, starts: &[usize], range: &Range<usize>) -> (usize, usize) {
+    let end = range.start + text[range.clone()].trim_end().len();
+    let line = |offset: usize| starts.partition_point(|&start| start <= offset) - 1;
+    (line(range.start), line(end.max(range.start + 1) - 1))
+}
+
+/// The text of a description without the indentation of its continuation
+/// lines or the blank lines around it.
+fn dedented(text: &str) -> String {
+    let mut lines = text.split('\n');
+    let first = lines.next().unwrap_or_default().trim();
+    let rest: Vec<&str> = lines.collect();
+    let common = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indentation(line))
+        .min()
+        .unwrap_or(0);
+    let rest = rest
+        .iter()
+        .map(|line| line.get(common..).unwrap_or_default().trim_end());
+    std::iter::once(first)
+        .chain(rest)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// The kind of the sections [`SectionSpan::is`] knows, e.g. `"args"`.
+fn kind_of(span: &SectionSpan) -> Option<&'static str> {
+    ["args", "attrs", "returns", "yields", "raises"]
+        .into_iter()
+        .find(|&kind| span.is(kind))
+}
+
+fn section_of(text: &str, starts: &[usize], style: DocstringStyle, span: &SectionSpan) -> Section {
+    let kind = kind_of(span);
+    let description = match span.name {
+        Some(_) => {
+            let end = span
+                .entries
+                .first()
+                .map_or(span.range.end, |entry| entry.range.start);
+            dedented(&text[span.body.min(end)..end])
+        }
+        None => dedented(&text[span.range.clone()]),
+    };
+    let (line_offset, end_line_offset) = line_offsets(text, starts, &span.range);
+    Section {
+        name: span.name.clone(),
+        kind: kind.map(str::to_string),
+        description,
+        // the indented lines of a paragraph are part of its description
+        entries: match span.name {
+            Some(_) => span
+                .entries
+                .iter()
+                .map(|entry| entry_of(text, starts, style, kind, entry))
+                .collect(),
+            None => Vec::new(),
+        },
+        line_offset,
+        end_line_offset,
+    }
+}
+
+/// Reads the type annotation of `entry` in the way of `style`: `x (int):`
+/// for Google, `x : int` for NumPy and `:param int x:` or `:type x: int`
+/// for Sphinx. A return value or yield without a name is only a type.
+fn entry_of(
+    text: &str,
+    starts: &[usize],
+    style: DocstringStyle,
+    kind: Option<&str>,
+    entry: &EntrySpan,
+) -> Entry {
+    let is_value = matches!(kind, Some("returns" | "yields"));
+    let head = &text[entry.range.start..entry.description.max(entry.range.start)];
+    let mut names = entry.names.clone();
+    let mut annotation = None;
+    let mut description = text[entry.description.min(entry.range.end)..entry.range.end].to_string();
+    match style {
+        DocstringStyle::Google => {
+            let head = head.trim_start().trim_start_matches("# ");
+            if let Some(caps) = _SUB_SECTION_PATTERN.captures(head) {
+                annotation = caps.get(2).map(|annotation| {
+                    let annotation = annotation.as_str().trim();
+                    annotation[1..annotation.len() - 1].trim().to_string()
+                });
+                if is_value && annotation.is_none() {
+                    // `int: The value.`, unless the colon is further along
+                    // the sentence
+                    if caps.get(0).unwrap().start() == 0 {
+                        annotation = names.pop();
+                    } else {
+                        names.clear();
+                        description = text[entry.range.clone()].to_string();
+                    }
+                }
+            } else {
+                description = text[entry.range.clone()].to_string();
+            }
+        }
+        DocstringStyle::Numpy => match head.split_once(':') {
+            _ if names.is_empty() => description = text[entry.range.clone()].to_string(),
+            Some((_, annotation_text)) => {
+                annotation = Some(annotation_text.trim().to_string()).filter(|a| !a.is_empty());
+            }
+            None if is_value => {
+                names.clear();
+                annotation = Some(head.trim().to_string());
+            }
+            None => {}
+        },
+        DocstringStyle::Sphinx => {
+            if let Some(caps) = SPHINX_FIELD_PATTERN.captures(head) {
+                let words: Vec<&str> = caps[2].split_whitespace().collect();
+                if words.len() > 1 {
+                    annotation = Some(words[..words.len() - 1].join(" "));
+                }
+            }
+            // `:type x: int` and `:rtype: int` give the type on a line of their own
+            let mut lines = Vec::new();
+            for line in description.split('\n') {
+                match SPHINX_FIELD_PATTERN.captures(line) {
+                    Some(caps) if ["type", "vartype", "rtype"].contains(&&caps[1]) => {
+                        let field_type = line[caps.get(0).unwrap().end()..].trim();
+                        if annotation.is_none() && !field_type.is_empty() {
+                            annotation = Some(field_type.to_string());
+                        }
+                    }
+                    _ => lines.push(line),
+                }
+            }
+            description = lines.join("\n");
+        }
+    }
+    let (line_offset, end_line_offset) = line_offsets(text, starts, &entry.range);
+    Entry {
+        names,
+        annotation,
+        description: dedented(&description),
+        line_offset,
+        end_line_offset,
+    }
 }
 
 #[pyfunction]
 pub fn _get_sections(lines: Vec<String>) -> Vec<_Section> {
-    let cleaned_lines: Vec<String> = lines
+    google_sections(&lines)
         .into_iter()
+        .map(|(section, _)| section)
+        .collect()
+}
+
+/// Splits a Google style docstring on its blank lines. Entries are the
+/// lines indented least in a section.
+fn google_sections(lines: &[String]) -> Vec<(_Section, SectionSpan)> {
+    let starts = line_starts(lines);
+    let cleaned_lines: Vec<&str> = lines
+        .iter()
         .map(|line| {
             if line.trim_start().starts_with("# ") {
-                line.trim_start()[2..].to_string()
+                &line.trim_start()[2..]
             } else {
-                line
+                line.as_str()
             }
         })
         .collect();
 
-    let mut sections: Vec<_Section> = Vec::new();
-    let mut lines = cleaned_lines.into_iter().peekable();
-
-    while let Some(line) = lines.find(|l| !l.trim().is_empty()) {
+    let mut sections = Vec::new();
+    let mut index = 0;
+    while index < cleaned_lines.len() {
+        if cleaned_lines[index].trim().is_empty() {
+            index += 1;
+            continue;
+        }
         // Check if it's a section name
+        let header = index;
         let section_name = SECTION_NAME_PATTERN
-            .captures(&line)
+            .captures(cleaned_lines[header])
             .and_then(|caps| caps.get(1).map(|m| m.as_str().to_string()));
 
-        let mut section_lines: Vec<String> = Vec::new();
-
         // Keep collecting lines until we hit a blank line or EOF
-        while let Some(peek) = lines.peek() {
-            if peek.trim().is_empty() {
-                // consume the empty line
-                lines.next();
-                break;
-            }
-            section_lines.push(lines.next().unwrap());
+        index += 1;
+        while index < cleaned_lines.len() && !cleaned_lines[index].trim().is_empty() {
+            index += 1;
         }
+        let section_lines = &cleaned_lines[header + 1..index];
 
         let subs = section_lines
             .iter()
@@ -290,13 +753,278 @@ pub fn _get_sections(lines: Vec<String>) -> Vec<_Section> {
             })
             .collect();
 
-        sections.push(_Section {
-            name: section_name,
-            subs,
+        let entry_indentation = section_lines.iter().map(|line| indentation(line)).min();
+        let mut entries: Vec<EntrySpan> = Vec::new();
+        for (line_index, line) in (header + 1..).zip(section_lines) {
+            let end = line_end(&starts, line_index);
+            if !entries.is_empty() && Some(indentation(line)) != entry_indentation {
+                entries.last_mut().unwrap().range.end = end;
+                continue;
+            }
+            // the `# ` taken off a commented line
+            let start = starts[line_index] + lines[line_index].len() - line.len();
+            let caps = _SUB_SECTION_PATTERN.captures(line);
+            entries.push(EntrySpan {
+                names: caps.iter().map(|caps| caps[1].to_string()).collect(),
+                range: starts[line_index]..end,
+                description: caps.map_or(end, |caps| start + caps.get(0).unwrap().end()),
+            });
+        }
+
+        sections.push((
+            _Section {
+                name: section_name.clone(),
+                subs,
+            },
+            SectionSpan {
+                name: section_name,
+                range: starts[header]..line_end(&starts, index - 1),
+                body: line_end(&starts, header),
+                entries,
+            },
+        ));
+    }
+
+    sections
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+#[cfg(test)]
+fn _get_numpy_sections(lines: Vec<String>) -> Vec<_Section> {
+    numpy_sections(&lines)
+        .into_iter()
+        .map(|(section, _)| section)
+        .collect()
+}
+
+/// Splits a NumPy style docstring on its underlined section names. Entries
+/// are the lines indented like the section name, their descriptions are
+/// indented further.
+fn numpy_sections(lines: &[String]) -> Vec<(_Section, SectionSpan)> {
+    let starts = line_starts(lines);
+    let is_header = |index: usize| {
+        index + 1 < lines.len()
+            && !lines[index].trim().is_empty()
+            && NUMPY_UNDERLINE_PATTERN.is_match(&lines[index + 1])
+    };
+
+    let mut sections = Vec::new();
+    let mut index = 0;
+    if !is_header(0) && lines.iter().any(|line| !line.trim().is_empty()) {
+        // the summary and description before the first section
+        let end = (0..lines.len())
+            .find(|&index| is_header(index))
+            .map_or(starts[lines.len()] - 1, |index| starts[index]);
+        sections.push((
+            _Section {
+                name: None,
+                subs: vec![],
+            },
+            SectionSpan {
+                name: None,
+                range: 0..end,
+                body: 0,
+                entries: vec![],
+            },
+        ));
+    }
+    while index < lines.len() {
+        if !is_header(index) {
+            index += 1;
+            continue;
+        }
+        let header = index;
+        let name = lines[header].trim().to_string();
+        let header_indentation = indentation(&lines[header]);
+        let body = line_end(&starts, header + 1);
+        index += 2;
+
+        let mut subs: Vec<String> = Vec::new();
+        let mut entries: Vec<EntrySpan> = Vec::new();
+        let mut section_end = body;
+        while index < lines.len() && !is_header(index) {
+            let line = &lines[index];
+            let line_index = index;
+            index += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            section_end = line_end(&starts, line_index);
+            if indentation(line) > header_indentation {
+                if let Some(entry) = entries.last_mut() {
+                    entry.range.end = section_end;
+                }
+                continue;
+            }
+            let names: Vec<String> = NUMPY_ENTRY_PATTERN
+                .captures(line)
+                .map(|caps| {
+                    caps[1]
+                        .split(',')
+                        .map(|entry| {
+                            let entry = entry.trim().trim_start_matches('*');
+                            // exceptions are matched by their name, without the module
+                            entry.rsplit('.').next().unwrap_or(entry).to_string()
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            subs.extend(names.iter().cloned());
+            entries.push(EntrySpan {
+                names,
+                range: starts[line_index]..section_end,
+                description: section_end,
+            });
+        }
+
+        sections.push((
+            _Section {
+                name: Some(name.clone()),
+                subs,
+            },
+            SectionSpan {
+                name: Some(name),
+                range: starts[header]..section_end,
+                body,
+                entries,
+            },
+        ));
+    }
+
+    sections
+}
+
+#[cfg(test)]
+fn _get_sphinx_sections(lines: Vec<String>) -> Vec<_Section> {
+    sphinx_sections(&lines)
+        .into_iter()
+        .map(|(section, _)| section)
+        .collect()
+}
+
+/// Turns the reST fields of a Sphinx style docstring into sections. Each
+/// `:param x:` style field is one entry, so the arguments, attributes and
+/// raises fields are collected into a single section each, while every
+/// `:returns:`/ `:yields:` field is a section of its own. An entry goes on
+/// over more indented lines and the `:type x:` field of its argument, or
+/// the `:rtype:` field of a return value.
+fn sphinx_sections(lines: &[String]) -> Vec<(_Section, SectionSpan)> {
+    let starts = line_starts(lines);
+    // the group of merged sections, `None` for returns and yields
+    let mut sections: Vec<(Option<&str>, _Section, SectionSpan)> = Vec::new();
+    // the section of the last entry and the indentation of its field
+    let mut current: Option<(usize, usize)> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let end = line_end(&starts, index);
+        let caps = SPHINX_FIELD_PATTERN.captures(line);
+        let field = caps.as_ref().map(|caps| caps[1].to_lowercase());
+        // `:param int x:` names `x`, `:raises mod.Error:` names `Error`
+        let entry = caps.as_ref().and_then(|caps| {
+            caps[2].split_whitespace().last().map(|entry| {
+                let entry = entry.trim_start_matches('*');
+                entry.rsplit('.').next().unwrap_or(entry).to_string()
+            })
         });
+
+        if let Some((position, field_indentation)) = current {
+            let span = &mut sections[position].2;
+            let last_names = &span.entries.last().unwrap().names;
+            let continued = match field.as_deref() {
+                None => !line.trim().is_empty() && indentation(line) > field_indentation,
+                Some("type" | "vartype") => entry.is_some() && last_names.first() == entry.as_ref(),
+                Some("rtype") => span.is("returns"),
+                Some(_) => false,
+            };
+            if continued {
+                span.range.end = end;
+                span.entries.last_mut().unwrap().range.end = end;
+                continue;
+            }
+            current = None;
+        }
+
+        let (Some(caps), Some(field)) = (caps, field) else {
+            continue;
+        };
+        let Some((&group, _)) = _SPHINX_FIELD_NAMES
+            .iter()
+            .find(|(_, names)| names.contains(field.as_str()))
+        else {
+            continue;
+        };
+        let entry_span = EntrySpan {
+            names: entry.iter().cloned().collect(),
+            range: starts[index]..end,
+            description: starts[index] + caps.get(0).unwrap().end(),
+        };
+
+        let position = if group == "returns" || group == "yields" {
+            None
+        } else {
+            sections.iter().position(|(name, ..)| *name == Some(group))
+        };
+        match position {
+            Some(position) => {
+                let (_, section, span) = &mut sections[position];
+                section.subs.extend(entry);
+                span.range.end = end;
+                span.entries.push(entry_span);
+                current = Some((position, indentation(line)));
+            }
+            None => {
+                let merged = group != "returns" && group != "yields";
+                let name = if merged { group.to_string() } else { field };
+                sections.push((
+                    merged.then_some(group),
+                    _Section {
+                        name: Some(name.clone()),
+                        subs: entry.into_iter().collect(),
+                    },
+                    SectionSpan {
+                        name: Some(name),
+                        range: starts[index]..end,
+                        body: starts[index],
+                        entries: vec![entry_span],
+                    },
+                ));
+                current = Some((sections.len() - 1, indentation(line)));
+            }
+        }
     }
 
+    // the merged sections come after the returns and yields
+    sections.sort_by_key(|(group, ..)| group.is_some());
     sections
+        .into_iter()
+        .map(|(_, section, span)| (section, span))
+        .collect()
+}
+
+/// The sections of the docstring `text` with their byte ranges in it, in
+/// `style` or the style [`detect_style`] finds. Empty when `text` has
+/// `\r\n` line endings.
+pub fn section_spans(text: &str, style: Option<DocstringStyle>) -> Vec<SectionSpan> {
+    if text.contains('\r') {
+        return Vec::new();
+    }
+    let lines: Vec<String> = text.split('\n').map(|line| line.to_string()).collect();
+    let style = style.unwrap_or_else(|| detect_style(&lines));
+    styled_sections(&lines, style)
+        .into_iter()
+        .map(|(_, span)| span)
+        .collect()
+}
+
+fn styled_sections(lines: &[String], style: DocstringStyle) -> Vec<(_Section, SectionSpan)> {
+    match style {
+        DocstringStyle::Google => google_sections(lines),
+        DocstringStyle::Numpy => numpy_sections(lines),
+        DocstringStyle::Sphinx => sphinx_sections(lines),
+    }
 }
 
 fn _get_section_by_name<'a>(name: &str, sections: &'a [_Section]) -> Option<&'a _Section> {
@@ -310,7 +1038,7 @@ fn _get_section_by_name<'a>(name: &str, sections: &'a [_Section]) -> Option<&'a
             .unwrap_or(false)
     })
 }
-fn _get_all_section_names_by_name<'a>(name: &str, sections: &'a [_Section]) -> Option<Vec<String>> {
+fn _get_all_section_names_by_name(name: &str, sections: &[_Section]) -> Option<Vec<String>> {
     let valid_names = &_SECTION_NAMES[name];
 
     let all_section_names: Vec<String> = sections
@@ -332,15 +1060,34 @@ fn _get_all_section_names_by_name<'a>(name: &str, sections: &'a [_Section]) -> O
     Some(all_section_names)
 }
 
-pub fn parse(constant_expr: &ExprConstant) -> Docstring {
+/// Parses a docstring written in `style`, or in the style [`detect_style`]
+/// finds when none is configured.
+pub fn parse(constant_expr: &ExprConstant, style: Option<DocstringStyle>) -> Docstring {
     let value = constant_expr.clone().value.expect_str();
-    let sections = _get_sections(value.lines().map(|line| line.to_string()).collect());
+    Docstring {
+        range: constant_expr.range,
+        ..parse_text(&value, style)
+    }
+}
+
+/// Parses the text of a docstring like [`parse`], the range is that of
+/// `text`.
+pub fn parse_text(text: &str, style: Option<DocstringStyle>) -> Docstring {
+    let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+    let style = style.unwrap_or_else(|| detect_style(&lines));
+    let (sections, mut spans): (Vec<_Section>, Vec<SectionSpan>) =
+        styled_sections(&lines, style).into_iter().unzip();
 
     let args_section = _get_section_by_name("args", &sections);
     let attrs_section = _get_section_by_name("attrs", &sections);
     let raises_section = _get_section_by_name("raises", &sections);
 
-    Docstring::new(
+    // the spans are offsets in the text joined back from the lines, in the
+    // order of the text rather than the Sphinx sections
+    let joined = lines.join("\n");
+    let starts = line_starts(&lines);
+    spans.sort_by_key(|span| span.range.start);
+    let docstring = Docstring::new(
         args_section.map(|s| s.subs.clone()),
         _get_all_section_names_by_name("args", &sections),
         attrs_section.map(|s| s.subs.clone()),
@@ -349,22 +1096,23 @@ pub fn parse(constant_expr: &ExprConstant) -> Docstring {
         _get_all_section_names_by_name("yields", &sections),
         raises_section.map(|s| s.subs.clone()),
         _get_all_section_names_by_name("raises", &sections),
-        constant_expr.range,
-    )
+        spans
+            .iter()
+            .map(|span| section_of(&joined, &starts, style, span))
+            .collect(),
+        TextRange::up_to(TextSize::of(text)),
+    );
+    Docstring { style, ..docstring }
 }
 
 ////////// Tests
 
+#[cfg(test)]
 struct TestInput {
     input: Vec<String>,
     expected: Option<Vec<_Section>>,
 }
 
-struct TestParseInput {
-    input: String,
-    expected: Docstring,
-}
-
 #[test]
 pub fn test_get_sections() {
     let test_inputs = [
@@ -493,3 +1241,247 @@ pub fn test_get_sections() {
         }
     }
 }
+
+#[cfg(test)]
+fn to_lines(text: &str) -> Vec<String> {
+    text.lines().map(|line| line.to_string()).collect()
+}
+
+#[test]
+pub fn test_detect_style() {
+    let google = "Summary.\n\n    Args:\n        arg_1: description\n";
+    let numpy = "Summary.\n\n    Parameters\n    ----------\n    arg_1 : int\n";
+    let sphinx = "Summary.\n\n    :param arg_1: description\n";
+    assert_eq!(detect_style(&to_lines(google)), DocstringStyle::Google);
+    assert_eq!(detect_style(&to_lines(numpy)), DocstringStyle::Numpy);
+    assert_eq!(detect_style(&to_lines(sphinx)), DocstringStyle::Sphinx);
+    assert_eq!(detect_style(&to_lines("Summary.")), DocstringStyle::Google);
+    assert_eq!(
+        DocstringStyle::from_name("NumPy"),
+        Some(DocstringStyle::Numpy)
+    );
+    assert_eq!(DocstringStyle::from_name("epytext"), None);
+}
+
+#[test]
+pub fn test_get_numpy_sections() {
+    let text = r#"Summary.
+
+    Parameters
+    ----------
+    arg_1 : int
+        Description 1.
+
+        More about arg_1.
+    *args, **kwargs
+        Description 2.
+
+    Returns
+    -------
+    int
+        The result.
+
+    Raises
+    ------
+    module.Exc1
+        Description.
+    "#;
+    let sections = _get_numpy_sections(to_lines(text));
+    assert_eq!(
+        sections,
+        vec![
+            _Section {
+                name: None,
+                subs: vec![],
+            },
+            _Section {
+                name: Some("Parameters".to_string()),
+                subs: vec![
+                    "arg_1".to_string(),
+                    "args".to_string(),
+                    "kwargs".to_string()
+                ],
+            },
+            _Section {
+                name: Some("Returns".to_string()),
+                subs: vec!["int".to_string()],
+            },
+            _Section {
+                name: Some("Raises".to_string()),
+                subs: vec!["Exc1".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+pub fn test_get_sphinx_sections() {
+    let text = r#"Summary.
+
+    :param arg_1: Description 1.
+    :param int arg_2: Description 2.
+    :type arg_2: int
+    :raises mod.Exc1: Description.
+    :returns: The result.
+    :rtype: int
+    :returns: Another result.
+    "#;
+    let sections = _get_sphinx_sections(to_lines(text));
+    assert_eq!(
+        sections,
+        vec![
+            _Section {
+                name: Some("returns".to_string()),
+                subs: vec![],
+            },
+            _Section {
+                name: Some("returns".to_string()),
+                subs: vec![],
+            },
+            _Section {
+                name: Some("args".to_string()),
+                subs: vec!["arg_1".to_string(), "arg_2".to_string()],
+            },
+            _Section {
+                name: Some("raises".to_string()),
+                subs: vec!["Exc1".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+pub fn test_section_spans() {
+    let text = "Summary.\n\n    Args:\n        arg_1: Description 1.\n            More.\n        arg_2: Description 2.\n    ";
+    let spans = section_spans(text, None);
+    assert_eq!(spans.len(), 2);
+    let args = &spans[1];
+    assert!(args.is("args"));
+    assert_eq!(&text[args.range.clone()], "    Args:\n        arg_1: Description 1.\n            More.\n        arg_2: Description 2.\n");
+    assert_eq!(&text[args.body..], &text[args.entries[0].range.start..]);
+    let entries: Vec<(&[String], &str, &str)> = args
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.names.as_slice(),
+                &text[entry.range.clone()],
+                &text[entry.description..entry.range.end],
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (
+                &["arg_1".to_string()][..],
+                "        arg_1: Description 1.\n            More.\n",
+                " Description 1.\n            More.\n"
+            ),
+            (
+                &["arg_2".to_string()][..],
+                "        arg_2: Description 2.\n",
+                " Description 2.\n"
+            ),
+        ]
+    );
+    assert!(section_spans("Summary.\r\n\r\nArgs:\r\n    arg_1: Description.", None).is_empty());
+}
+
+#[test]
+pub fn test_parse_text() {
+    fn entries(docstring: &Docstring, kind: &str) -> Vec<(Vec<String>, Option<String>, String)> {
+        docstring
+            .entries_of(kind)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.names, entry.annotation, entry.description))
+            .collect()
+    }
+    fn entry(
+        names: &[&str],
+        annotation: Option<&str>,
+        description: &str,
+    ) -> (Vec<String>, Option<String>, String) {
+        (
+            names.iter().map(|name| name.to_string()).collect(),
+            annotation.map(str::to_string),
+            description.to_string(),
+        )
+    }
+
+    let google = parse_text(
+        "Summary.\n\nArgs:\n    arg_1 (int): First,\n        continued.\n    arg_2: Second.\n\nReturns:\n    bool: Whether it worked.\n",
+        None,
+    );
+    assert_eq!(google.get_args(), vec!["arg_1", "arg_2"]);
+    assert_eq!(
+        entries(&google, "args"),
+        vec![
+            entry(&["arg_1"], Some("int"), "First,\ncontinued."),
+            entry(&["arg_2"], None, "Second."),
+        ]
+    );
+    assert_eq!(
+        entries(&google, "returns"),
+        vec![entry(&[], Some("bool"), "Whether it worked.")]
+    );
+    let offsets: Vec<(usize, usize)> = google
+        .sections
+        .iter()
+        .map(|section| (section.line_offset, section.end_line_offset))
+        .collect();
+    assert_eq!(offsets, vec![(0, 0), (2, 5), (7, 8)]);
+    let first = &google.sections[1].entries[0];
+    assert_eq!((first.line_offset, first.end_line_offset), (3, 4));
+    assert_eq!(google.get_range(), TextRange::up_to(TextSize::new(117)));
+
+    let google = parse_text("Returns:\n    The value: it is.", None);
+    assert_eq!(
+        entries(&google, "returns"),
+        vec![entry(&[], None, "The value: it is.")]
+    );
+
+    let numpy = parse_text(
+        "Summary.\n\nParameters\n----------\nx1, x2 : int\n    The inputs.\nflag\n\nReturns\n-------\nint\n    The sum.\n",
+        None,
+    );
+    assert_eq!(
+        entries(&numpy, "args"),
+        vec![
+            entry(&["x1", "x2"], Some("int"), "The inputs."),
+            entry(&["flag"], None, ""),
+        ]
+    );
+    assert_eq!(
+        entries(&numpy, "returns"),
+        vec![entry(&[], Some("int"), "The sum.")]
+    );
+
+    // in the order of the text, with the `:type x:` and `:rtype:` fields
+    let sphinx = parse_text(
+        "Summary.\n\n:param int x: The x.\n:param y: The y\n    goes on.\n:type y: list[str]\n:returns: The result.\n:rtype: bool\n",
+        Some(DocstringStyle::Sphinx),
+    );
+    let kinds: Vec<Option<String>> = sphinx
+        .sections
+        .iter()
+        .map(|section| section.kind.clone())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![Some("args".to_string()), Some("returns".to_string())]
+    );
+    assert_eq!(
+        entries(&sphinx, "args"),
+        vec![
+            entry(&["x"], Some("int"), "The x."),
+            entry(&["y"], Some("list[str]"), "The y\ngoes on."),
+        ]
+    );
+    assert_eq!(
+        entries(&sphinx, "returns"),
+        vec![entry(&[], Some("bool"), "The result.")]
+    );
+    assert!(sphinx.entries_of("yields").is_none());
+}
@@ -0,0 +1,64 @@
+use crate::config::Config;
+use ignore::WalkBuilder;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Lists the files to lint under `path`, honoring `.gitignore` and `.ignore`
+/// files and the `include` and `exclude` settings. Symbolic links are
+/// followed, a link back to one of its parents is skipped.
+fn get_files_recursively(path: &Path, config: &Config) -> Vec<String> {
+    // the filter has to own its settings
+    let excluded = config.clone();
+    let walker = WalkBuilder::new(path)
+        .hidden(false)
+        .require_git(false)
+        .follow_links(true)
+        .filter_entry(move |entry| {
+            entry.depth() == 0
+                || entry
+                    .path()
+                    .to_str()
+                    .is_some_and(|path| !excluded.is_excluded(path))
+        })
+        .build();
+
+    let mut py_files = Vec::new();
+    // unreadable directories and symlink loops are skipped
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        if let Some(path_str) = entry.path().to_str() {
+            if config.is_included(path_str) {
+                py_files.push(path_str.to_string());
+            }
+        }
+    }
+    py_files
+}
+
+/// The files to lint for `paths`, a file reached through several paths is
+/// only linted once.
+pub fn collect_files(paths: &[PathBuf], config: &Config) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for path in paths {
+        let found = if path.is_dir() {
+            get_files_recursively(path, config)
+        } else {
+            path.to_str()
+                .map(|path| path.to_string())
+                .into_iter()
+                .collect()
+        };
+        for file in found {
+            let key = Path::new(&file)
+                .canonicalize()
+                .unwrap_or_else(|_| PathBuf::from(&file));
+            if seen.insert(key) {
+                files.push(file);
+            }
+        }
+    }
+    files
+}
@@ -0,0 +1,1057 @@
+use crate::config::Config;
+use crate::constants::{
+    arg_in_docstr_code, arg_not_in_docstr_code, docstr_missing_code, duplicate_arg_in_docstr_code,
+    duplicate_exc_code, exc_in_docstr_code, exc_not_in_docstr_code,
+};
+use crate::diagnostic::{Applicability, Diagnostic, Edit, Fix};
+use crate::docstring::{detect_style, section_spans, DocstringStyle, EntrySpan, SectionSpan};
+use crate::plugin::{DocstringCollector, FunctionInfo};
+use crate::rule_engine::{
+    catch_check_panic, check_error_output, cleanse_args, get_exc_id, is_yield_empty,
+    lint_bytes_with_config, lint_code_with_config, read_error_output,
+};
+use crate::source::{decode_source_with_encoding, encode_source};
+use rayon::prelude::*;
+use rustpython_ast::text_size::TextRange;
+use rustpython_ast::Ranged;
+use similar::TextDiff;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::ops::Range;
+
+/// Fixing can uncover new fixable problems, e.g. a new docstring section,
+/// give up after this many rounds.
+const MAX_FIX_ROUNDS: usize = 10;
+
+/// The outcome of fixing a file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FixResult {
+    /// The problems left after fixing.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The number of problems fixed.
+    pub fixed: usize,
+    /// The source before and after fixing, `None` when nothing changed.
+    pub change: Option<SourceChange>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceChange {
+    pub original: String,
+    pub fixed: String,
+    /// `fixed` encoded like the original file.
+    pub bytes: Vec<u8>,
+}
+
+impl SourceChange {
+    /// The change as a unified diff of `file_name`.
+    pub fn unified_diff(&self, file_name: &str) -> String {
+        TextDiff::from_lines(&self.original, &self.fixed)
+            .unified_diff()
+            .header(file_name, file_name)
+            .to_string()
+    }
+}
+
+/// Attaches a fix to the diagnostics of `code` that `--fix` can resolve.
+pub fn attach_fixes(
+    code: &str,
+    things: &DocstringCollector,
+    style: Option<DocstringStyle>,
+    output: &mut [Diagnostic],
+) {
+    let functions: Vec<&FunctionInfo> = things
+        .function_infos
+        .iter()
+        .chain(
+            things
+                .class_infos
+                .iter()
+                .flat_map(|class| class.funcs.iter()),
+        )
+        .collect();
+    let quotes = docstring_quotes(code, things);
+
+    for diagnostic in output.iter_mut() {
+        // the function the problem was found in
+        let Some(definition) = diagnostic.definition else {
+            continue;
+        };
+        let Some(function) = functions.iter().find(|function| {
+            let range = function.def.range();
+            function.def.name() == diagnostic.symbol
+                && (range.start().to_usize(), range.end().to_usize()) == definition
+        }) else {
+            continue;
+        };
+        let rule = diagnostic.code.as_str();
+        if rule == docstr_missing_code() {
+            diagnostic.fix = missing_docstring_fix(code, function, style, quotes);
+        } else if rule == arg_not_in_docstr_code()
+            || rule == arg_in_docstr_code()
+            || rule == duplicate_arg_in_docstr_code()
+        {
+            diagnostic.fix = args_fix(code, function, style);
+        } else if rule == exc_not_in_docstr_code() || rule == duplicate_exc_code() {
+            diagnostic.fix = raises_fix(code, function, style);
+        } else if rule == exc_in_docstr_code() {
+            diagnostic.fix = extra_raises_fix(code, function, style);
+        }
+    }
+}
+
+/// Applies the fixes of `diagnostics` that do not overlap, unsafe ones only
+/// with `unsafe_fixes`. Returns the new source and the number of fixes.
+pub fn apply_fixes(code: &str, diagnostics: &[Diagnostic], unsafe_fixes: bool) -> (String, usize) {
+    let mut fixes: Vec<&Fix> = diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.fix.as_ref())
+        .filter(|fix| unsafe_fixes || fix.applicability == Applicability::Safe)
+        .filter(|fix| !fix.edits.is_empty())
+        .collect();
+    fixes.sort_by_key(|fix| fix.edits.iter().map(|edit| edit.start).min());
+
+    let mut edits: Vec<&Edit> = Vec::new();
+    let mut applied_fixes: Vec<&Fix> = Vec::new();
+    let mut applied = 0;
+    let mut last_end = 0;
+    for fix in fixes {
+        // one fix can resolve several problems, e.g. a whole Args section
+        if applied_fixes.contains(&fix) {
+            applied += 1;
+            continue;
+        }
+        let mut fix_edits: Vec<&Edit> = fix.edits.iter().collect();
+        fix_edits.sort_by_key(|edit| (edit.start, edit.end));
+        // another fix already rewrites this part of the source
+        if fix_edits[0].start < last_end || (applied > 0 && fix_edits[0].start == last_end) {
+            continue;
+        }
+        last_end = fix_edits
+            .iter()
+            .map(|edit| edit.end)
+            .max()
+            .unwrap_or(last_end);
+        edits.extend(fix_edits);
+        applied_fixes.push(fix);
+        applied += 1;
+    }
+
+    let mut fixed = String::with_capacity(code.len());
+    let mut position = 0;
+    for edit in edits {
+        fixed.push_str(&code[position..edit.start]);
+        fixed.push_str(&edit.content);
+        position = edit.end;
+    }
+    fixed.push_str(&code[position..]);
+    (fixed, applied)
+}
+
+/// Lints and fixes `code` until no fixable problem is left.
+pub fn fix_code_with_config(
+    code: &str,
+    file_name: Option<&str>,
+    config: &Config,
+    unsafe_fixes: bool,
+) -> (String, Vec<Diagnostic>, usize) {
+    let mut code = code.to_string();
+    let mut fixed = 0;
+    for _ in 0..MAX_FIX_ROUNDS {
+        let output = lint_code_with_config(&code, file_name, config);
+        let (new_code, applied) = apply_fixes(&code, &output, unsafe_fixes);
+        if applied == 0 {
+            break;
+        }
+        code = new_code;
+        fixed += applied;
+    }
+    let output = lint_code_with_config(&code, file_name, config);
+    (code, output, fixed)
+}
+
+/// Fixes the file at `file_name`, without writing it.
+pub fn fix_file_with_config(file_name: &str, config: &Config, unsafe_fixes: bool) -> FixResult {
+    let bytes = match fs::read(file_name) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            return FixResult {
+                diagnostics: read_error_output(error.to_string().as_str(), Some(file_name), config),
+                ..FixResult::default()
+            };
+        }
+    };
+    let Ok((original, encoding)) = decode_source_with_encoding(&bytes) else {
+        // reported as DCO004
+        return FixResult {
+            diagnostics: lint_bytes_with_config(&bytes, Some(file_name), config),
+            ..FixResult::default()
+        };
+    };
+
+    let (fixed_code, diagnostics, fixed) =
+        fix_code_with_config(&original, Some(file_name), config, unsafe_fixes);
+    if fixed_code == original {
+        return FixResult {
+            diagnostics,
+            ..FixResult::default()
+        };
+    }
+    match encode_source(&fixed_code, encoding) {
+        Some(bytes) => FixResult {
+            diagnostics,
+            fixed,
+            change: Some(SourceChange {
+                original,
+                fixed: fixed_code,
+                bytes,
+            }),
+        },
+        // the fixes use characters the file's encoding does not have
+        None => FixResult {
+            diagnostics: lint_code_with_config(&original, Some(file_name), config),
+            ..FixResult::default()
+        },
+    }
+}
+
+/// Like [`crate::rule_engine::lint_files`], fixing every file.
+pub fn fix_files(
+    mut files: Vec<String>,
+    config: &Config,
+    unsafe_fixes: bool,
+) -> Vec<(String, FixResult)> {
+    files.sort();
+    files.dedup();
+    files
+        .into_par_iter()
+        .map(|file| {
+            let result = catch_check_panic(|| fix_file_with_config(&file, config, unsafe_fixes))
+                .unwrap_or_else(|reason| FixResult {
+                    diagnostics: check_error_output(&reason, Some(file.as_str()), config),
+                    ..FixResult::default()
+                });
+            (file, result)
+        })
+        .collect()
+}
+
+/// `"""` unless most docstrings of the module use `'''`.
+fn docstring_quotes(code: &str, things: &DocstringCollector) -> &'static str {
+    let docstrings = things
+        .function_infos
+        .iter()
+        .chain(
+            things
+                .class_infos
+                .iter()
+                .flat_map(|class| class.funcs.iter()),
+        )
+        .filter_map(|function| function.docstring.as_ref())
+        .chain(
+            things
+                .class_infos
+                .iter()
+                .filter_map(|class| class.docstring.as_ref()),
+        );
+    let (mut single, mut double) = (0, 0);
+    for docstring in docstrings {
+        let start = docstring.get_range().start().to_usize();
+        let text = code[start.min(code.len())..].trim_start_matches(['r', 'R', 'u', 'U']);
+        if text.starts_with("'''") {
+            single += 1;
+        } else if text.starts_with("\"\"\"") {
+            double += 1;
+        }
+    }
+    if single > double {
+        "'''"
+    } else {
+        "\"\"\""
+    }
+}
+
+fn line_ending(code: &str) -> &'static str {
+    if code.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// The argument names of `function` in signature order, with the `*` and
+/// `**` of variadic arguments.
+fn documented_args(function: &FunctionInfo) -> Vec<String> {
+    let args = cleanse_args(function.def.args(), true);
+    let mut names: Vec<String> = Vec::new();
+    names.extend(args.posonlyargs.iter().map(|arg| arg.def.arg.to_string()));
+    names.extend(args.args.iter().map(|arg| arg.def.arg.to_string()));
+    names.extend(args.vararg.iter().map(|arg| format!("*{}", arg.arg)));
+    names.extend(args.kwonlyargs.iter().map(|arg| arg.def.arg.to_string()));
+    names.extend(args.kwarg.iter().map(|arg| format!("**{}", arg.arg)));
+    names
+}
+
+/// The exceptions `function` raises by name, in order of appearance.
+fn raised_exceptions(function: &FunctionInfo) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for raise in &function.raises {
+        if let Some(name) = get_exc_id(raise.clone()) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// A section header in `style`, with `entries` given as `(name,
+/// description)`.
+fn section_lines(style: DocstringStyle, section: &str, entries: &[(String, &str)]) -> Vec<String> {
+    let mut lines = Vec::new();
+    match style {
+        DocstringStyle::Google => lines.push(format!("{}:", section)),
+        DocstringStyle::Numpy => {
+            let section = if section == "Args" {
+                "Parameters"
+            } else {
+                section
+            };
+            lines.push(section.to_string());
+            lines.push("-".repeat(section.len()));
+        }
+        // Sphinx fields have no header
+        DocstringStyle::Sphinx => {}
+    }
+    let indent = if style == DocstringStyle::Google {
+        "    "
+    } else {
+        ""
+    };
+    for (name, description) in entries {
+        lines.extend(
+            entry_lines(style, section, name, description)
+                .into_iter()
+                .map(|line| format!("{}{}", indent, line)),
+        );
+    }
+    lines
+}
+
+/// An entry of `section` in `style`, an empty `name` for the entries of
+/// returns and yields.
+fn entry_lines(style: DocstringStyle, section: &str, name: &str, description: &str) -> Vec<String> {
+    match style {
+        DocstringStyle::Google => {
+            if name.is_empty() {
+                vec![description.to_string()]
+            } else {
+                vec![format!("{}: {}", name, description)]
+            }
+        }
+        DocstringStyle::Numpy => {
+            let name = if name.is_empty() { "object" } else { name };
+            vec![name.to_string(), format!("    {}", description)]
+        }
+        DocstringStyle::Sphinx => {
+            let field = match section {
+                "Args" => "param",
+                "Returns" => "returns",
+                "Yields" => "yields",
+                _ => "raises",
+            };
+            if name.is_empty() {
+                vec![format!(":{}: {}", field, description)]
+            } else {
+                vec![format!(
+                    ":{} {}: {}",
+                    field,
+                    name.trim_start_matches('*'),
+                    description
+                )]
+            }
+        }
+    }
+}
+
+/// DCO010: a placeholder docstring with the sections the other checks
+/// expect, inserted before the first statement of the body.
+fn missing_docstring_fix(
+    code: &str,
+    function: &FunctionInfo,
+    style: Option<DocstringStyle>,
+    quotes: &str,
+) -> Option<Fix> {
+    let style = style.unwrap_or(DocstringStyle::Google);
+    let body_start = function.def.body().first()?.range().start().to_usize();
+    let line_start = code[..body_start].rfind('\n').map_or(0, |index| index + 1);
+    let indent = &code[line_start..body_start];
+    // `def function_1(): pass` has no line of its own to put it on
+    if !indent.chars().all(|c| c == ' ' || c == '\t') {
+        return None;
+    }
+
+    let todo = "TODO.";
+    let mut sections: Vec<Vec<String>> = Vec::new();
+    let args: Vec<(String, &str)> = documented_args(function)
+        .into_iter()
+        .map(|name| (name, todo))
+        .collect();
+    if !args.is_empty() {
+        sections.push(section_lines(style, "Args", &args));
+    }
+    if function.returns.iter().any(|ret| ret.value.is_some()) {
+        sections.push(section_lines(style, "Returns", &[(String::new(), todo)]));
+    }
+    if function
+        .yields
+        .iter()
+        .any(|yield_kind| !is_yield_empty(&code, yield_kind))
+    {
+        sections.push(section_lines(style, "Yields", &[(String::new(), todo)]));
+    }
+    let raises: Vec<(String, &str)> = raised_exceptions(function)
+        .into_iter()
+        .map(|name| (name, todo))
+        .collect();
+    if !raises.is_empty() {
+        sections.push(section_lines(style, "Raises", &raises));
+    }
+
+    let newline = line_ending(code);
+    let mut lines = vec![format!(
+        "{}{}TODO: Document {}.",
+        indent,
+        quotes,
+        function.def.name()
+    )];
+    // Sphinx fields form a single block
+    let separate = style != DocstringStyle::Sphinx;
+    for (index, section) in sections.iter().enumerate() {
+        if index == 0 || separate {
+            lines.push(String::new());
+        }
+        lines.extend(section.iter().map(|line| format!("{}{}", indent, line)));
+    }
+    lines.push(format!("{}{}", indent, quotes));
+    let mut content = lines.join(newline);
+    content.push_str(newline);
+
+    Some(Fix::safe(vec![Edit::insertion(line_start, content)]))
+}
+
+/// The text between the quotes of the docstring at `range`, with the
+/// offset it starts at in `code`. `None` when the text is not the value of
+/// the docstring as written, e.g. with escapes.
+pub(crate) fn docstring_text(code: &str, range: TextRange) -> Option<(usize, &str)> {
+    let literal = code.get(range.start().to_usize()..range.end().to_usize())?;
+    let body = literal.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let prefix = &literal[..literal.len() - body.len()];
+    let quotes = ["\"\"\"", "'''", "\"", "'"]
+        .into_iter()
+        .find(|quotes| body.starts_with(quotes))?;
+    if body.len() < 2 * quotes.len() || !body.ends_with(quotes) {
+        return None;
+    }
+    let text = &body[quotes.len()..body.len() - quotes.len()];
+    // implicitly concatenated strings
+    if text.contains(quotes) {
+        return None;
+    }
+    if text.contains('\\') && !prefix.contains(['r', 'R']) {
+        return None;
+    }
+    Some((range.start().to_usize() + prefix.len() + quotes.len(), text))
+}
+
+/// The range to delete to drop `entry`, with the newline before it when it
+/// ends the docstring so the closing quotes stay where they were.
+fn entry_deletion(text: &str, entry: &EntrySpan) -> Range<usize> {
+    if entry.range.end == text.len() && !text.ends_with('\n') && entry.range.start > 0 {
+        entry.range.start - 1..entry.range.end
+    } else {
+        entry.range.clone()
+    }
+}
+
+/// `lines` to insert at `position`, which either starts a line or ends the
+/// docstring.
+fn inserted_lines(text: &str, position: usize, lines: &[String]) -> String {
+    if position == 0 || text[..position].ends_with('\n') {
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    } else {
+        lines.iter().map(|line| format!("\n{}", line)).collect()
+    }
+}
+
+fn leading_whitespace(text: &str) -> &str {
+    &text[..text.len() - text.trim_start_matches([' ', '\t']).len()]
+}
+
+/// The indentation of new entries in `section`.
+fn entry_indent(text: &str, section: &SectionSpan, style: DocstringStyle) -> String {
+    match section.entries.first() {
+        Some(entry) => leading_whitespace(&text[entry.range.start..]).to_string(),
+        None => {
+            let header = leading_whitespace(&text[section.range.start..]);
+            match style {
+                DocstringStyle::Google => format!("{}    ", header),
+                _ => header.to_string(),
+            }
+        }
+    }
+}
+
+/// The description of `repeated` as lines to add below `first`, nothing
+/// when the description of `first` already says it.
+fn merged_description(text: &str, first: &EntrySpan, repeated: &EntrySpan) -> Vec<String> {
+    let description =
+        |entry: &EntrySpan| &text[entry.description.min(entry.range.end)..entry.range.end];
+    let words = |description: &str| description.split_whitespace().collect::<Vec<_>>().join(" ");
+    let added = description(repeated);
+    if words(description(first)).contains(&words(added)) {
+        return Vec::new();
+    }
+
+    // below the description of the first entry, or indented past its name
+    let entry_text = &text[first.range.clone()];
+    let indent = match entry_text
+        .lines()
+        .skip(1)
+        .find(|line| !line.trim().is_empty())
+    {
+        Some(line) => leading_whitespace(line).to_string(),
+        None => format!("{}    ", leading_whitespace(entry_text)),
+    };
+    let lines: Vec<&str> = added.lines().map(|line| line.trim()).collect();
+    let first_line = lines.iter().position(|line| !line.is_empty()).unwrap_or(0);
+    let last_line = lines.iter().rposition(|line| !line.is_empty()).unwrap_or(0);
+    lines[first_line..=last_line]
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect()
+}
+
+/// How the entries of a section differ from the names the code has.
+struct SectionEdits<'a> {
+    /// Where `text` starts in the source.
+    offset: usize,
+    /// The text of the docstring.
+    text: &'a str,
+    /// Entries of names the code does not have.
+    unknown: Vec<Range<usize>>,
+    /// Entries repeating a name, merged into the first one.
+    repeated: Vec<Range<usize>>,
+    /// Stubs for the missing names and the merged descriptions.
+    insertions: BTreeMap<usize, Vec<String>>,
+}
+
+impl<'a> SectionEdits<'a> {
+    /// Compares the `kind` section of the docstring of `function` with
+    /// `expected`. A stub for a missing name goes after the entry of the
+    /// name before it. `None` without exactly one such section.
+    fn new(
+        code: &'a str,
+        function: &FunctionInfo,
+        style: Option<DocstringStyle>,
+        kind: &str,
+        expected: &[String],
+    ) -> Option<SectionEdits<'a>> {
+        let docstring = function.docstring.as_ref()?;
+        let (offset, text) = docstring_text(code, docstring.get_range())?;
+        let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+        let style = style.unwrap_or_else(|| detect_style(&lines));
+        let sections = section_spans(text, Some(style));
+        let mut matching = sections.iter().filter(|section| section.is(kind));
+        let section = matching.next()?;
+        // several sections have to be merged by hand first
+        if matching.next().is_some() {
+            return None;
+        }
+
+        let names: Vec<&str> = expected
+            .iter()
+            .map(|name| name.trim_start_matches('*'))
+            .collect();
+        let mut edits = SectionEdits {
+            offset,
+            text,
+            unknown: Vec::new(),
+            repeated: Vec::new(),
+            insertions: BTreeMap::new(),
+        };
+        // the first entry of every documented name
+        let mut documented: HashMap<&str, &EntrySpan> = HashMap::new();
+        for entry in &section.entries {
+            match entry.names.as_slice() {
+                [] => {}
+                [name] if !names.contains(&name.as_str()) => {
+                    edits.unknown.push(entry_deletion(text, entry));
+                }
+                [name] => match documented.get(name.as_str()) {
+                    Some(first) => {
+                        edits.repeated.push(entry_deletion(text, entry));
+                        let lines = merged_description(text, first, entry);
+                        edits
+                            .insertions
+                            .entry(first.range.end)
+                            .or_default()
+                            .extend(lines);
+                    }
+                    None => {
+                        documented.insert(name, entry);
+                    }
+                },
+                // `arg_1, arg_2 : int` documents several names in one line
+                several => {
+                    for name in several {
+                        documented.entry(name).or_insert(entry);
+                    }
+                }
+            }
+        }
+
+        let header = if kind == "args" { "Args" } else { "Raises" };
+        let indent = entry_indent(text, section, style);
+        for (index, name) in expected.iter().enumerate() {
+            if documented.contains_key(names[index]) {
+                continue;
+            }
+            let position = match names[..index]
+                .iter()
+                .rev()
+                .find_map(|previous| documented.get(previous))
+            {
+                Some(previous) => previous.range.end,
+                None => section
+                    .entries
+                    .first()
+                    .map_or(section.body, |entry| entry.range.start),
+            };
+            let stub = entry_lines(style, header, name, "TODO.")
+                .into_iter()
+                .map(|line| format!("{}{}", indent, line));
+            edits.insertions.entry(position).or_default().extend(stub);
+        }
+        Some(edits)
+    }
+
+    /// The source edits removing `deletions`, with the insertions when
+    /// `insert` is set.
+    fn edits(&self, insert: bool, deletions: &[Range<usize>]) -> Vec<Edit> {
+        let mut edits: Vec<Edit> = Vec::new();
+        for (&position, lines) in &self.insertions {
+            if !insert || lines.is_empty() {
+                continue;
+            }
+            // not in the middle of an entry that is removed
+            let position = deletions
+                .iter()
+                .find(|deletion| deletion.start < position && position < deletion.end)
+                .map_or(position, |deletion| deletion.start);
+            let content = inserted_lines(self.text, position, lines);
+            edits.push(Edit::insertion(self.offset + position, content));
+        }
+        edits.extend(deletions.iter().map(|deletion| {
+            Edit::deletion(self.offset + deletion.start, self.offset + deletion.end)
+        }));
+        edits
+    }
+}
+
+/// The fix `make` builds from `edits`, `None` when there is nothing to do.
+fn fix_of(edits: Vec<Edit>, make: fn(Vec<Edit>) -> Fix) -> Option<Fix> {
+    if edits.is_empty() {
+        return None;
+    }
+    Some(make(edits))
+}
+
+/// DCO023, DCO024 and DCO025: brings the Args section in line with the
+/// signature. Stubs are added for the undocumented arguments, entries of
+/// arguments that do not exist are removed and a repeated entry is merged
+/// into the first one.
+fn args_fix(code: &str, function: &FunctionInfo, style: Option<DocstringStyle>) -> Option<Fix> {
+    let signature = documented_args(function);
+    let edits = SectionEdits::new(code, function, style, "args", &signature)?;
+    let deletions = [edits.unknown.as_slice(), edits.repeated.as_slice()].concat();
+    fix_of(edits.edits(true, &deletions), Fix::safe)
+}
+
+/// DCO053 and DCO056: adds stubs for the exceptions the Raises section
+/// misses and merges repeated entries.
+fn raises_fix(code: &str, function: &FunctionInfo, style: Option<DocstringStyle>) -> Option<Fix> {
+    let raised = raised_exceptions(function);
+    let edits = SectionEdits::new(code, function, style, "raises", &raised)?;
+    fix_of(edits.edits(true, &edits.repeated), Fix::safe)
+}
+
+/// DCO054: removes the entries of exceptions that are never raised. Unsafe,
+/// the exception may come from a function called here.
+fn extra_raises_fix(
+    code: &str,
+    function: &FunctionInfo,
+    style: Option<DocstringStyle>,
+) -> Option<Fix> {
+    // a bare `raise` or `raise error` can raise anything
+    if function
+        .raises
+        .iter()
+        .any(|raise| get_exc_id(raise.clone()).is_none())
+    {
+        return None;
+    }
+    let raised = raised_exceptions(function);
+    let edits = SectionEdits::new(code, function, style, "raises", &raised)?;
+    fix_of(edits.edits(false, &edits.unknown), Fix::unsafe_edits)
+}
+
+////////// Tests
+
+#[cfg(test)]
+fn fix(code: &str, convention: Option<DocstringStyle>) -> (String, Vec<Diagnostic>) {
+    let config = Config {
+        convention,
+        ..Config::default()
+    };
+    let (fixed, output, _) = fix_code_with_config(code, None, &config, false);
+    (fixed, output)
+}
+
+#[test]
+pub fn test_missing_docstring_fix_google() {
+    let code = r#"
+def function_1(arg_1, *args, _private=None, **kwargs):
+    if arg_1:
+        raise ValueError("bad")
+    return 1
+
+
+class Class1:
+    '''Docstring 1.'''
+
+    def method_1(self):
+        yield 1
+"#;
+    // the quotes follow the other docstrings of the module
+    let (fixed, output) = fix(code, None);
+    assert_eq!(
+        fixed,
+        r#"
+def function_1(arg_1, *args, _private=None, **kwargs):
+    '''TODO: Document function_1.
+
+    Args:
+        arg_1: TODO.
+        *args: TODO.
+        **kwargs: TODO.
+
+    Returns:
+        TODO.
+
+    Raises:
+        ValueError: TODO.
+    '''
+    if arg_1:
+        raise ValueError("bad")
+    return 1
+
+
+class Class1:
+    '''Docstring 1.'''
+
+    def method_1(self):
+        '''TODO: Document method_1.
+
+        Yields:
+            TODO.
+        '''
+        yield 1
+"#
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+}
+
+#[test]
+pub fn test_missing_docstring_fix_numpy_and_sphinx() {
+    let code = "def function_1(arg_1):\n    return arg_1\n";
+    let (fixed, output) = fix(code, Some(DocstringStyle::Numpy));
+    assert_eq!(
+        fixed,
+        "def function_1(arg_1):\n    \"\"\"TODO: Document function_1.\n\n    Parameters\n    ----------\n    arg_1\n        TODO.\n\n    Returns\n    -------\n    object\n        TODO.\n    \"\"\"\n    return arg_1\n"
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+
+    let (fixed, output) = fix(code, Some(DocstringStyle::Sphinx));
+    assert_eq!(
+        fixed,
+        "def function_1(arg_1):\n    \"\"\"TODO: Document function_1.\n\n    :param arg_1: TODO.\n    :returns: TODO.\n    \"\"\"\n    return arg_1\n"
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+}
+
+#[test]
+pub fn test_missing_docstring_fix_skips_one_liners() {
+    let code = "def function_1(): pass\n";
+    let (fixed, output) = fix(code, None);
+    assert_eq!(fixed, code);
+    assert_eq!(output.len(), 1);
+}
+
+#[test]
+pub fn test_args_fix_google() {
+    let code = r#"
+def function_1(arg_1, arg_2, *args, arg_3=None):
+    """Docstring 1.
+
+    Some text: kept as it is.
+
+    Args:
+        arg_1 (int): Description 1.
+            More about arg_1.
+        old_arg: Gone.
+        arg_1: Said again.
+        arg_3: Description 3.
+
+    Returns:
+        Something.
+    """
+    return arg_1
+"#;
+    let (fixed, output) = fix(code, None);
+    assert_eq!(
+        fixed,
+        r#"
+def function_1(arg_1, arg_2, *args, arg_3=None):
+    """Docstring 1.
+
+    Some text: kept as it is.
+
+    Args:
+        arg_1 (int): Description 1.
+            More about arg_1.
+            Said again.
+        arg_2: TODO.
+        *args: TODO.
+        arg_3: Description 3.
+
+    Returns:
+        Something.
+    """
+    return arg_1
+"#
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+}
+
+#[test]
+pub fn test_args_fix_closing_quotes_on_last_entry() {
+    let code = "def function_1(arg_1, arg_2):\n    \"\"\"Docstring 1.\n\n    Args:\n        arg_1: Description 1.\n        arg_3: Gone.\"\"\"\n";
+    let (fixed, output) = fix(code, None);
+    assert_eq!(
+        fixed,
+        "def function_1(arg_1, arg_2):\n    \"\"\"Docstring 1.\n\n    Args:\n        arg_1: Description 1.\n        arg_2: TODO.\"\"\"\n"
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+}
+
+#[test]
+pub fn test_args_fix_numpy_and_sphinx() {
+    let code = r#"
+def function_1(arg_1, arg_2):
+    """Docstring 1.
+
+    Parameters
+    ----------
+    arg_2 : int
+        Description 2.
+
+        More about arg_2.
+    arg_2
+        Description 2.
+    arg_3 : str
+        Gone.
+
+    Returns
+    -------
+    int
+        The result.
+    """
+    return arg_1
+"#;
+    let (fixed, output) = fix(code, None);
+    assert_eq!(
+        fixed,
+        r#"
+def function_1(arg_1, arg_2):
+    """Docstring 1.
+
+    Parameters
+    ----------
+    arg_1
+        TODO.
+    arg_2 : int
+        Description 2.
+
+        More about arg_2.
+
+    Returns
+    -------
+    int
+        The result.
+    """
+    return arg_1
+"#
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+
+    let code = r#"
+def function_1(arg_1, arg_2):
+    """Docstring 1.
+
+    :param arg_1: Description 1.
+    :param arg_3: Gone,
+        on two lines.
+    :type arg_3: str
+    :returns: The result.
+    """
+    return arg_1
+"#;
+    let (fixed, output) = fix(code, None);
+    assert_eq!(
+        fixed,
+        r#"
+def function_1(arg_1, arg_2):
+    """Docstring 1.
+
+    :param arg_1: Description 1.
+    :param arg_2: TODO.
+    :returns: The result.
+    """
+    return arg_1
+"#
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+}
+
+#[test]
+pub fn test_raises_fix() {
+    let code = r#"
+def function_1(arg_1):
+    """Docstring 1.
+
+    Args:
+        arg_1: Description 1.
+
+    Raises:
+        KeyError: Never raised.
+        ValueError: When arg_1 is bad.
+        ValueError: When arg_1 is bad.
+    """
+    if arg_1:
+        raise TypeError("bad")
+    raise errors.ValueError("bad")
+"#;
+    let (fixed, output) = fix(code, None);
+    let safe = r#"
+def function_1(arg_1):
+    """Docstring 1.
+
+    Args:
+        arg_1: Description 1.
+
+    Raises:
+        TypeError: TODO.
+        KeyError: Never raised.
+        ValueError: When arg_1 is bad.
+    """
+    if arg_1:
+        raise TypeError("bad")
+    raise errors.ValueError("bad")
+"#;
+    assert_eq!(fixed, safe);
+    // the extra exception is only removed with unsafe fixes
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].code, "D054");
+    assert_eq!(
+        output[0].fix.as_ref().map(|fix| fix.applicability),
+        Some(Applicability::Unsafe)
+    );
+
+    let config = Config::default();
+    let (fixed, output, _) = fix_code_with_config(code, None, &config, true);
+    assert_eq!(fixed, safe.replace("        KeyError: Never raised.\n", ""));
+    assert!(output.is_empty(), "{:#?}", output);
+}
+
+#[test]
+pub fn test_unsafe_raises_fix_numpy_and_sphinx() {
+    let numpy = r#"
+def function_1():
+    """Docstring 1.
+
+    Raises
+    ------
+    KeyError
+        Never raised.
+    ValueError
+        When it is bad.
+    """
+    raise ValueError("bad")
+"#;
+    let sphinx = r#"
+def function_1():
+    """Docstring 1.
+
+    :raises KeyError: Never raised.
+    :raises ValueError: When it is bad.
+    """
+    raise ValueError("bad")
+"#;
+    let config = Config::default();
+    let (fixed, output, _) = fix_code_with_config(numpy, None, &config, true);
+    assert_eq!(
+        fixed,
+        numpy.replace("    KeyError\n        Never raised.\n", "")
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+
+    let (fixed, output, _) = fix_code_with_config(sphinx, None, &config, true);
+    assert_eq!(
+        fixed,
+        sphinx.replace("    :raises KeyError: Never raised.\n", "")
+    );
+    assert!(output.is_empty(), "{:#?}", output);
+}
+
+#[test]
+pub fn test_raises_fix_keeps_entries_with_bare_raise() {
+    let code = r#"
+def function_1():
+    """Docstring 1.
+
+    :raises KeyError: Raised by something else.
+    """
+    try:
+        raise ValueError("bad")
+    except ValueError:
+        raise
+"#;
+    let config = Config {
+        convention: Some(DocstringStyle::Sphinx),
+        ..Config::default()
+    };
+    let (fixed, _, _) = fix_code_with_config(code, None, &config, true);
+    assert_eq!(
+        fixed,
+        code.replace(
+            "    :raises KeyError:",
+            "    :raises ValueError: TODO.\n    :raises KeyError:"
+        )
+    );
+}
@@ -1,11 +1,20 @@
+use diagnostic::Diagnostic;
 use pyo3::prelude::*;
 
-mod docstring;
+pub mod docstring;
 //::{parse, Docstring, _get_sections};
 
+pub mod config;
 pub mod constants;
+pub mod diagnostic;
+mod files;
+pub mod fix;
+mod noqa;
 mod plugin;
+mod python;
 pub mod rule_engine;
+mod source;
+#[cfg(test)]
 mod test_rule_engine;
 
 #[pyfunction]
@@ -26,9 +35,17 @@ fn _core(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(hello_from_bin, m)?)?;
     m.add_function(wrap_pyfunction!(my_hello, m)?)?;
     m.add_function(wrap_pyfunction!(rule_engine::apply_rules, m)?)?;
+    m.add_function(wrap_pyfunction!(python::lint_source, m)?)?;
+    m.add_function(wrap_pyfunction!(python::lint_paths, m)?)?;
+    m.add_class::<Diagnostic>()?;
 
     let submodule = PyModule::new_bound(py, "docstring")?;
     submodule.add_class::<docstring::_Section>()?;
+    submodule.add_class::<docstring::Docstring>()?;
+    submodule.add_class::<docstring::Section>()?;
+    submodule.add_class::<docstring::Entry>()?;
+    submodule.add_function(wrap_pyfunction!(docstring::_get_sections, &submodule)?)?;
+    submodule.add_function(wrap_pyfunction!(python::parse_docstring, &submodule)?)?;
 
     m.add_submodule(&submodule)?;
     let constants = PyModule::new_bound(py, "constants")?;
@@ -40,4 +57,3 @@ fn _core(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     Ok(())
 }
-
@@ -1,94 +1,553 @@
-use clap::Parser;
-use std::fs;
+use baseline::Baseline;
+use clap::{Parser, Subcommand, ValueEnum};
+use config::Config;
+use diagnostic::Diagnostic;
+use diff::ChangedLines;
+use glob::Pattern;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+mod baseline;
+mod config;
 mod constants;
+// the full diagnostic API is for library consumers, the CLI only renders it
+#[allow(dead_code)]
+mod diagnostic;
+mod diff;
 mod docstring;
+mod files;
+mod fix;
+mod noqa;
 mod plugin;
+mod report;
 mod rule_engine;
+mod server;
+mod source;
+
+/// Exit status when no problems were found (or `--exit-zero` was given).
+const EXIT_CLEAN: i32 = 0;
+/// Exit status when at least one diagnostic was reported.
+const EXIT_VIOLATIONS: i32 = 1;
+/// Exit status for usage and IO errors, matching clap's usage errors.
+const EXIT_ERROR: i32 = 2;
+
 /// 🐍 vipyrdocs — Fast. Lethal. Python docstring checks.
 #[derive(Parser, Debug)]
 #[command(
     name = "vipyrdocs",
     version = "0.1.0",
     about = "🐍 vipyrdocs — Fast. Lethal. Python docstring checks.",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
     long_about = r#"
 vipyrdocs  — Fast. Lethal. Python docstring checks.
 
 Usage:
-  vipyrdocs <PATH> [options]
+  vipyrdocs <PATH>... [options]
+  vipyrdocs server
+
+Commands:
+  server              Run a language server on stdin and stdout, for
+                      diagnostics and quick fixes in any editor
 
 Arguments:
-  <PATH>              Path to a Python file or directory
+  <PATH>...           Python files or directories, `-` reads the source
+                      from stdin
 
 Options:
+  --format <FORMAT>   Output format: text, json, jsonl or sarif [default: text]
+  --config <FILE>     Read settings from FILE instead of the nearest
+                      vipyrdocs.toml or pyproject.toml [tool.vipyrdocs]
+  --select <CODES>    Comma-separated rule codes or prefixes to enable
+  --extend-select <CODES>
+                      Comma-separated rule codes or prefixes to enable on
+                      top of the selected ones
+  --ignore <CODES>    Comma-separated rule codes or prefixes to disable
+  --exclude <PATTERNS>
+                      Comma-separated globs of files and directories to
+                      skip, replacing the default list
+  --extend-exclude <PATTERNS>
+                      Comma-separated globs to skip on top of the
+                      excluded ones
+  --stdin-filename <FILE>
+                      File name of the source read from stdin, used for
+                      the configuration, per-file ignores and the report
+  --fix               Fix what can be fixed automatically, e.g. insert
+                      docstring skeletons, and report the rest
+  --unsafe-fixes      With --fix or --diff, also apply fixes that can
+                      change what a docstring says, e.g. drop exceptions
+                      never raised
+  --diff              Print the unified diff --fix would apply without
+                      writing it, exit with status 1 if anything would
+                      change
+  --write-baseline <FILE>
+                      Record the current problems in FILE and exit
+  --baseline <FILE>   Do not report the problems recorded in FILE, and
+                      list the recorded ones that are fixed
+  --diff-base <REV>   Only lint the files changed since the git revision
+                      REV and report the problems in functions and
+                      classes with changed lines
+  -j, --jobs <N>      Number of files to lint in parallel [default: number
+                      of CPUs]
+  --exit-zero         Exit with status 0 even if problems were found
   -h, --help          Show this help message and exit
   -V, --version       Show version info and exit
 
 Examples:
   vipyrdocs my_script.py
   vipyrdocs ./src
+  vipyrdocs ./src ./tests/conftest.py
+  vipyrdocs ./src --format json
+  vipyrdocs ./src --select DCO02 --ignore DCO025
+  vipyrdocs ./src --extend-select DCO05
+  vipyrdocs ./src --diff-base origin/main
+  cat module.py | vipyrdocs - --stdin-filename src/module.py
+
+Exit status:
+  0  no problems found
+  1  problems found
+  2  usage or IO error, or a file could not be read
 
 🔥 Strike out undocumented code with precision.
 "#
 )]
 struct Cli {
-    /// Path to a Python file or directory to check
-    path: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Python files or directories to check, `-` reads from stdin
+    #[arg(required = true, value_name = "PATH")]
+    paths: Vec<PathBuf>,
+
+    /// File name of the source read from stdin
+    #[arg(long, value_name = "FILE")]
+    stdin_filename: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Exit with status 0 even if problems were found
+    #[arg(long)]
+    exit_zero: bool,
+
+    /// Configuration file, instead of the nearest vipyrdocs.toml or pyproject.toml
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Rule codes or prefixes to enable, replaces `select` from the configuration
+    #[arg(long, value_name = "CODES", value_delimiter = ',')]
+    select: Option<Vec<String>>,
+
+    /// Rule codes or prefixes to enable on top of `select`, added to `extend-select` from the configuration
+    #[arg(long, value_name = "CODES", value_delimiter = ',')]
+    extend_select: Vec<String>,
+
+    /// Rule codes or prefixes to disable, added to `ignore` from the configuration
+    #[arg(long, value_name = "CODES", value_delimiter = ',')]
+    ignore: Vec<String>,
+
+    /// Glob patterns of files and directories to skip, replaces `exclude` from the configuration
+    #[arg(long, value_name = "PATTERNS", value_delimiter = ',')]
+    exclude: Option<Vec<String>>,
+
+    /// Glob patterns of files and directories to skip, added to `extend-exclude` from the configuration
+    #[arg(long, value_name = "PATTERNS", value_delimiter = ',')]
+    extend_exclude: Vec<String>,
+
+    /// Number of files to lint in parallel [default: number of CPUs]
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
+    /// Fix the problems that can be fixed automatically and report the rest
+    #[arg(long)]
+    fix: bool,
+
+    /// Also apply fixes that can change what a docstring says, with `--fix` or `--diff`
+    #[arg(long)]
+    unsafe_fixes: bool,
+
+    /// Print the diff of the fixes instead of writing them, exit 1 if there are any
+    #[arg(long, conflicts_with_all = ["fix", "write_baseline"])]
+    diff: bool,
+
+    /// Record the current problems in FILE and exit, see `--baseline`
+    #[arg(long, value_name = "FILE", conflicts_with = "baseline")]
+    write_baseline: Option<PathBuf>,
+
+    /// Do not report the problems recorded in FILE with `--write-baseline`
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Only lint files changed since this git revision and report problems in changed definitions
+    #[arg(long, value_name = "REV")]
+    diff_base: Option<String>,
 }
 
-fn get_files_recursively(path: PathBuf) -> Vec<String> {
-    let mut py_files = Vec::new();
-    visit_dirs(&path, &mut py_files);
-    py_files
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a language server on stdin and stdout
+    Server,
 }
 
-fn visit_dirs(dir: &Path, py_files: &mut Vec<String>) {
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                visit_dirs(&path, py_files);
-            } else if let Some(ext) = path.extension() {
-                if ext == "py" {
-                    if let Some(path_str) = path.to_str() {
-                        py_files.push(path_str.to_string());
-                    }
-                }
-            }
-        }
-    }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human readable report
+    Text,
+    /// A single JSON array with one object per diagnostic
+    Json,
+    /// One JSON object per line
+    Jsonl,
+    /// SARIF 2.1.0 log, e.g. for GitHub code scanning
+    Sarif,
 }
 
 fn main() {
     let cli = Cli::parse();
+    if let Some(Command::Server) = cli.command {
+        if let Err(err) = server::run() {
+            eprintln!("❌ Error: {}", err);
+            std::process::exit(EXIT_ERROR);
+        }
+        std::process::exit(EXIT_CLEAN);
+    }
+    let from_stdin = cli.paths.iter().any(|path| path.as_os_str() == "-");
 
-    if !cli.path.exists() {
-        eprintln!("❌ Error: Path '{}' does not exist.", cli.path.display());
-        std::process::exit(1);
+    if from_stdin && cli.paths.len() > 1 {
+        eprintln!("❌ Error: stdin (-) can not be combined with other paths.");
+        std::process::exit(EXIT_ERROR);
+    }
+    if cli.stdin_filename.is_some() && !from_stdin {
+        eprintln!("❌ Error: --stdin-filename only applies when reading from stdin (-).");
+        std::process::exit(EXIT_ERROR);
+    }
+    let missing: Vec<&PathBuf> = cli
+        .paths
+        .iter()
+        .filter(|path| !from_stdin && !path.exists())
+        .collect();
+    if !missing.is_empty() {
+        for path in missing {
+            eprintln!("❌ Error: Path '{}' does not exist.", path.display());
+        }
+        std::process::exit(EXIT_ERROR);
     }
 
-    println!("🐍 Scanning path: {}", cli.path.display());
+    let config = match load_config(&cli) {
+        Ok(config) => config,
+        Err(errors) => {
+            for error in errors {
+                eprintln!("❌ Error: {}", error);
+            }
+            std::process::exit(EXIT_ERROR);
+        }
+    };
 
-    // TODO: Call your core logic here
-    // _core::check_docstrings(cli.path);
-    if cli.path.is_dir() {
-        let files = get_files_recursively(cli.path);
+    let display_paths = match (&cli.stdin_filename, from_stdin) {
+        (Some(name), true) => vec![name.clone()],
+        _ => cli.paths.clone(),
+    };
 
-        println!("🐍 Scan result: ");
-        for file in files {
-            let output = rule_engine::lint_file("", Some(file.as_str()));
-            println!("{}: ", file);
+    if from_stdin && (cli.fix || cli.diff) {
+        eprintln!(
+            "❌ Error: {} can not be used with stdin (-).",
+            if cli.fix { "--fix" } else { "--diff" }
+        );
+        std::process::exit(EXIT_ERROR);
+    }
+    if cli.unsafe_fixes && !cli.fix && !cli.diff {
+        eprintln!("❌ Error: --unsafe-fixes requires --fix or --diff.");
+        std::process::exit(EXIT_ERROR);
+    }
+    if from_stdin && (cli.baseline.is_some() || cli.write_baseline.is_some()) {
+        eprintln!("❌ Error: a baseline can not be used with stdin (-).");
+        std::process::exit(EXIT_ERROR);
+    }
+    let baseline = cli
+        .baseline
+        .as_ref()
+        .map(|path| match Baseline::load(path) {
+            Ok(baseline) => baseline,
+            Err(err) => {
+                eprintln!("❌ Error: {}", err);
+                std::process::exit(EXIT_ERROR);
+            }
+        });
+
+    let changed = match &cli.diff_base {
+        Some(_) if from_stdin => {
+            eprintln!("❌ Error: --diff-base can not be used with stdin (-).");
+            std::process::exit(EXIT_ERROR);
+        }
+        Some(base) => match ChangedLines::since(base, &cli.paths) {
+            Ok(changed) => Some(changed),
+            Err(err) => {
+                eprintln!("❌ Error: {}", err);
+                std::process::exit(EXIT_ERROR);
+            }
+        },
+        None => None,
+    };
+
+    let mut files: Vec<String> = if from_stdin {
+        Vec::new()
+    } else {
+        files::collect_files(&cli.paths, &config)
+    };
+    if let Some(changed) = &changed {
+        files.retain(|file| changed.contains_file(file));
+    }
+
+    let pool = match rayon::ThreadPoolBuilder::new()
+        .num_threads(cli.jobs.map_or(0, usize::from))
+        .build()
+    {
+        Ok(pool) => pool,
+        Err(err) => {
+            eprintln!("❌ Error: could not start the worker threads: {}", err);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+    let results: Vec<(String, Vec<Diagnostic>)> = if from_stdin {
+        let mut source = Vec::new();
+        if let Err(err) = io::stdin().read_to_end(&mut source) {
+            eprintln!("❌ Error: could not read stdin: {}", err);
+            std::process::exit(EXIT_ERROR);
+        }
+        let file_name = display_paths[0].to_string_lossy().to_string();
+        let output = rule_engine::lint_bytes_with_config(&source, Some(&file_name), &config);
+        vec![(file_name, output)]
+    } else if cli.diff {
+        let fixed = pool.install(|| fix::fix_files(files, &config, cli.unsafe_fixes));
+        print_diffs(fixed)
+    } else if cli.fix {
+        let fixed = pool.install(|| fix::fix_files(files, &config, cli.unsafe_fixes));
+        write_fixes(fixed)
+    } else {
+        pool.install(|| rule_engine::lint_files(files, &config))
+    };
+    let results = match &changed {
+        Some(changed) => results
+            .into_iter()
+            .map(|(file, output)| {
+                let output = changed.retain_changed(&file, output);
+                (file, output)
+            })
+            .collect(),
+        None => results,
+    };
 
-            for line in output {
-                println!("  - {}", line);
+    if let Some(path) = &cli.write_baseline {
+        let baseline = Baseline::from_results(path, &results);
+        if let Err(err) = baseline.write(path) {
+            eprintln!("❌ Error: {}", err);
+            std::process::exit(EXIT_ERROR);
+        }
+        println!(
+            "✅ Wrote {} baseline entries to {}",
+            baseline.len(),
+            path.display()
+        );
+        std::process::exit(EXIT_CLEAN);
+    }
+    let results = match &baseline {
+        Some(baseline) => {
+            let (results, stale) = baseline.filter(results);
+            for entry in &stale {
+                eprintln!(
+                    "⚠️ Stale baseline entry: {} {} {} ({} fixed), rewrite the baseline to drop it",
+                    entry.file, entry.code, entry.symbol, entry.count
+                );
             }
+            results
+        }
+        None => results,
+    };
+
+    let found_problems = results.iter().any(|(_, output)| !output.is_empty());
+    let unreadable = results.iter().any(|(_, output)| {
+        output
+            .iter()
+            .any(|diagnostic| diagnostic.code == constants::read_error_code())
+    });
+
+    let mut stdout = io::stdout().lock();
+    let written = match cli.format {
+        OutputFormat::Text => {
+            print_text_report(&display_paths, &results);
+            Ok(())
         }
-    } else if cli.path.is_file() {
-        let output = rule_engine::lint_file("", cli.path.to_str());
+        OutputFormat::Json => report::write_json(&mut stdout, &flatten(results)),
+        OutputFormat::Jsonl => report::write_jsonl(&mut stdout, &flatten(results)),
+        OutputFormat::Sarif => report::write_sarif(&mut stdout, &flatten(results)),
+    };
+    if let Err(err) = written {
+        eprintln!("❌ Error: could not write the report: {}", err);
+        std::process::exit(EXIT_ERROR);
+    }
 
-        println!("🐍 Scan result: ");
-        for line in output {
-            println!("  - {}", line);
+    // a file that could not be read was not checked, `--exit-zero` or not
+    if unreadable {
+        std::process::exit(EXIT_ERROR);
+    }
+    if found_problems && !cli.exit_zero {
+        std::process::exit(EXIT_VIOLATIONS);
+    }
+    std::process::exit(EXIT_CLEAN);
+}
+
+/// Writes the fixed files and returns the problems left in each.
+fn write_fixes(fixed: Vec<(String, fix::FixResult)>) -> Vec<(String, Vec<Diagnostic>)> {
+    let mut fixed_problems = 0;
+    let mut fixed_files = 0;
+    let results = fixed
+        .into_iter()
+        .map(|(file, result)| {
+            if let Some(change) = &result.change {
+                if let Err(err) = std::fs::write(&file, &change.bytes) {
+                    eprintln!("❌ Error: could not write {}: {}", file, err);
+                    std::process::exit(EXIT_ERROR);
+                }
+                fixed_problems += result.fixed;
+                fixed_files += 1;
+            }
+            (file, result.diagnostics)
+        })
+        .collect();
+    if fixed_problems > 0 {
+        eprintln!(
+            "🔧 Fixed {} problems in {} files",
+            fixed_problems, fixed_files
+        );
+    }
+    results
+}
+
+/// Prints the diff of every file `--fix` would change and exits, with
+/// status 1 when there is any.
+fn print_diffs(fixed: Vec<(String, fix::FixResult)>) -> ! {
+    let mut fixed_problems = 0;
+    let mut fixed_files = 0;
+    let mut unreadable = false;
+    for (file, result) in &fixed {
+        if let Some(change) = &result.change {
+            print!("{}", change.unified_diff(file));
+            fixed_problems += result.fixed;
+            fixed_files += 1;
+        }
+        for diagnostic in &result.diagnostics {
+            if diagnostic.code == constants::read_error_code() {
+                eprintln!("❌ Error: {}: {}", file, diagnostic.message);
+                unreadable = true;
+            }
+        }
+    }
+    if fixed_problems > 0 {
+        eprintln!(
+            "🔧 Would fix {} problems in {} files",
+            fixed_problems, fixed_files
+        );
+    }
+
+    if unreadable {
+        std::process::exit(EXIT_ERROR);
+    }
+    if fixed_files > 0 {
+        std::process::exit(EXIT_VIOLATIONS);
+    }
+    std::process::exit(EXIT_CLEAN);
+}
+
+/// Loads the configuration file and applies the command line overrides on
+/// top of it.
+fn load_config(cli: &Cli) -> Result<Config, Vec<String>> {
+    let path = match &cli.config {
+        Some(path) => Some(path.clone()),
+        None if cli.paths[0].as_os_str() == "-" => {
+            Config::find(cli.stdin_filename.as_deref().unwrap_or(Path::new(".")))
+        }
+        None => Config::find_common(&cli.paths).map_err(|error| vec![error])?,
+    };
+    let mut config = match path {
+        Some(path) => Config::load(&path).map_err(|errors| {
+            errors
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>()
+        })?,
+        None => Config::default(),
+    };
+
+    let mut errors = Vec::new();
+    if let Some(exclude) = &cli.exclude {
+        config.exclude = parse_patterns("--exclude", exclude, &mut errors);
+    }
+    config.extend_exclude.extend(parse_patterns(
+        "--extend-exclude",
+        &cli.extend_exclude,
+        &mut errors,
+    ));
+    if let Some(select) = &cli.select {
+        config.select = Some(parse_codes("--select", select, &mut errors));
+    }
+    config.extend_select.extend(parse_codes(
+        "--extend-select",
+        &cli.extend_select,
+        &mut errors,
+    ));
+    config
+        .ignore
+        .extend(parse_codes("--ignore", &cli.ignore, &mut errors));
+    if errors.is_empty() {
+        Ok(config)
+    } else {
+        Err(errors)
+    }
+}
+
+fn parse_patterns(flag: &str, patterns: &[String], errors: &mut Vec<String>) -> Vec<Pattern> {
+    let mut parsed = Vec::new();
+    for pattern in patterns {
+        match Pattern::new(pattern) {
+            Ok(compiled) => parsed.push(compiled),
+            Err(err) => errors.push(format!("invalid glob \"{}\" in {}: {}", pattern, flag, err)),
+        }
+    }
+    parsed
+}
+
+fn parse_codes(flag: &str, codes: &[String], errors: &mut Vec<String>) -> Vec<String> {
+    let rules = constants::all_rules();
+    let mut parsed = Vec::new();
+    for code in codes.iter().filter(|code| !code.trim().is_empty()) {
+        let normalized = constants::normalize_code(code);
+        if normalized == "ALL" || rules.iter().any(|(rule, _)| rule.starts_with(&normalized)) {
+            parsed.push(normalized);
+        } else {
+            errors.push(format!(
+                "unknown rule code or prefix \"{}\" in {}",
+                code, flag
+            ));
+        }
+    }
+    parsed
+}
+
+fn flatten(results: Vec<(String, Vec<Diagnostic>)>) -> Vec<Diagnostic> {
+    results.into_iter().flat_map(|(_, output)| output).collect()
+}
+
+fn print_text_report(paths: &[PathBuf], results: &[(String, Vec<Diagnostic>)]) {
+    for path in paths {
+        println!("🐍 Scanning path: {}", path.display());
+    }
+    println!("🐍 Scan result: ");
+    let show_files = paths.len() > 1 || paths.iter().any(|path| path.is_dir());
+    for (file, output) in results {
+        if show_files {
+            println!("{}: ", file);
+        }
+        for diagnostic in output {
+            println!("  - {}", diagnostic);
         }
     }
 }
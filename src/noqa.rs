@@ -0,0 +1,184 @@
+use crate::constants::{is_own_code, normalize_code};
+use regex::Regex;
+use rustpython_parser::lexer::lex;
+use rustpython_parser::{Mode, Tok};
+
+lazy_static::lazy_static! {
+    // `# noqa`, `# noqa: DCO020` or `# noqa: DCO020, DCO030`
+    static ref NOQA_PATTERN: Regex = Regex::new(
+        r"(?i)#\s*noqa(?:\s*:\s*(?P<codes>[A-Z]+[0-9]+(?:\s*,\s*[A-Z]+[0-9]+)*))?"
+    )
+    .unwrap();
+}
+
+lazy_static::lazy_static! {
+    static ref FILE_NOQA_PATTERN: Regex = Regex::new(r"(?i)^#\s*vipyrdocs\s*:\s*noqa\b").unwrap();
+}
+
+/// A `# noqa` comment, `codes` is `None` for a blanket `# noqa` and only
+/// holds the codes of this tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoqaDirective {
+    pub line: usize,
+    pub column: usize,
+    pub end_column: usize,
+    pub codes: Option<Vec<String>>,
+}
+
+/// The suppression comments of a module.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NoqaComments {
+    /// A `# vipyrdocs: noqa` comment turns off every check for the module.
+    pub file_level: bool,
+    pub directives: Vec<NoqaDirective>,
+}
+
+impl NoqaComments {
+    pub fn on_line(&self, line: usize) -> impl Iterator<Item = &NoqaDirective> {
+        self.directives
+            .iter()
+            .filter(move |directive| directive.line == line)
+    }
+}
+
+/// Collects the `# noqa` comments of `code`. Only real comments count, the
+/// same text inside a string is ignored, see [`parse_docstring_noqa`] for
+/// docstrings.
+pub fn parse_noqa_comments(code: &str) -> NoqaComments {
+    let mut comments = NoqaComments::default();
+
+    // the lexer keeps repeating its first error, the parser reports it
+    for (tok, range) in lex(code, Mode::Module).map_while(Result::ok) {
+        let Tok::Comment(text) = tok else {
+            continue;
+        };
+        if FILE_NOQA_PATTERN.is_match(&text) {
+            comments.file_level = true;
+            continue;
+        }
+        comments
+            .directives
+            .extend(find_directive(code, range.start().to_usize(), &text));
+    }
+
+    comments
+}
+
+/// Finds a `# noqa` written on the first line of a multi-line docstring,
+/// `offset` is where the docstring starts in `code`.
+pub fn parse_docstring_noqa(code: &str, offset: usize) -> Option<NoqaDirective> {
+    let rest = &code[offset.min(code.len())..];
+    let first_line = rest.split('\n').next().unwrap_or_default();
+    find_directive(code, offset, first_line)
+}
+
+fn find_directive(code: &str, offset: usize, text: &str) -> Option<NoqaDirective> {
+    let captures = NOQA_PATTERN.captures(text)?;
+    let found = captures.get(0).unwrap();
+    let (line, column) = line_and_column(code, offset + found.start());
+    let (_, end_column) = line_and_column(code, offset + found.end());
+    let codes = captures.name("codes").map(|codes| {
+        codes
+            .as_str()
+            .split(',')
+            // the codes of other tools are theirs to check
+            .filter(|code| is_own_code(code))
+            .map(normalize_code)
+            .collect::<Vec<String>>()
+    });
+    Some(NoqaDirective {
+        line,
+        column,
+        end_column,
+        codes,
+    })
+}
+
+/// The 1-based line of the `:` that ends the signature of a definition,
+/// `start` and `body_start` are where the definition and its body start in
+/// `code`.
+pub fn signature_end_line(code: &str, start: usize, body_start: usize) -> usize {
+    let signature = &code[start..body_start.max(start)];
+    let mut depth = 0;
+    let mut colon = None;
+    for (tok, range) in lex(signature, Mode::Module).map_while(Result::ok) {
+        match tok {
+            Tok::Lpar | Tok::Lsqb | Tok::Lbrace => depth += 1,
+            Tok::Rpar | Tok::Rsqb | Tok::Rbrace => depth -= 1,
+            Tok::Colon if depth == 0 => colon = Some(range.start().to_usize()),
+            _ => {}
+        }
+    }
+    line_and_column(code, start + colon.unwrap_or_default()).0
+}
+
+/// 1-based line and 0-based column of a byte offset.
+pub fn line_and_column(code: &str, offset: usize) -> (usize, usize) {
+    let before = &code[..offset.min(code.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map(|idx| before[idx + 1..].chars().count())
+        .unwrap_or(before.chars().count());
+    (line, column)
+}
+
+////////// Tests
+
+#[test]
+pub fn test_parse_noqa_comments() {
+    let code = r#"
+def function_1():  # noqa
+    """Docstring 1.  # noqa: DCO030"""
+
+def function_2(arg_1):  # NOQA:DCO020, dco030
+    pass
+"#;
+    let comments = parse_noqa_comments(code);
+    assert!(!comments.file_level);
+    assert_eq!(
+        comments.directives,
+        vec![
+            NoqaDirective {
+                line: 2,
+                column: 19,
+                end_column: 25,
+                codes: None,
+            },
+            NoqaDirective {
+                line: 5,
+                column: 24,
+                end_column: 45,
+                codes: Some(vec!["D020".to_string(), "D030".to_string()]),
+            },
+        ]
+    );
+    assert_eq!(comments.on_line(5).count(), 1);
+}
+
+#[test]
+pub fn test_parse_docstring_noqa() {
+    let code =
+        "def function_1(arg_1):\n    \"\"\"Docstring 1.  # noqa: DCO020\n\n    More.\n    \"\"\"\n";
+    let directive = parse_docstring_noqa(code, code.find("\"\"\"").unwrap()).unwrap();
+    assert_eq!((directive.line, directive.column), (2, 21));
+    assert_eq!(directive.codes, Some(vec!["D020".to_string()]));
+    assert_eq!(parse_docstring_noqa(code, code.find("More").unwrap()), None);
+}
+
+#[test]
+pub fn test_parse_file_level_noqa() {
+    let comments = parse_noqa_comments("# vipyrdocs: noqa\n\ndef function_1():\n    pass\n");
+    assert!(comments.file_level);
+    assert!(comments.directives.is_empty());
+    assert!(!parse_noqa_comments("x = 1  # noqa\n").file_level);
+}
+
+#[test]
+pub fn test_signature_end_line() {
+    let code = "def function_1(\n    arg_1: dict[str, int] = {1: 2},\n) -> int:  # noqa\n    return arg_1\n";
+    let body_start = code.find("return").unwrap();
+    assert_eq!(signature_end_line(code, 0, body_start), 3);
+    let code = "class Class1: pass\n";
+    assert_eq!(signature_end_line(code, 0, code.find("pass").unwrap()), 1);
+}
@@ -1,31 +1,39 @@
 use crate::docstring;
-use crate::docstring::Docstring;
+use crate::docstring::{Docstring, DocstringStyle};
 use rustpython_ast::text_size::TextRange;
 use rustpython_ast::{
-    Arguments, ExprYield, ExprYieldFrom, Stmt, StmtAsyncFunctionDef, StmtClassDef, StmtFunctionDef,
-    StmtRaise, StmtReturn, Visitor,
+    Arguments, ExprYield, ExprYieldFrom, Stmt, StmtAnnAssign, StmtAssign, StmtAsyncFunctionDef,
+    StmtAugAssign, StmtClassDef, StmtFunctionDef, StmtRaise, StmtReturn, Visitor,
 };
-use rustpython_parser::{parse, Mode};
+use rustpython_parser::{parse, Mode, ParseError};
 
 use rustpython_ast::Expr;
 
-pub fn get_result(code: &str, filename: Option<&str>) -> DocstringCollector {
+/// Parses `code` and collects its functions and classes, docstrings are read
+/// in `style` or in the style detected for each of them.
+pub fn get_result(
+    code: &str,
+    filename: Option<&str>,
+    style: Option<DocstringStyle>,
+) -> Result<DocstringCollector, ParseError> {
     let filename = filename.unwrap_or("<embedded>");
-    let tree = parse(code, Mode::Interactive, filename);
-    let tree_mod = tree.unwrap();
-    let body = &tree_mod.as_interactive().unwrap().body;
+    let tree_mod = parse(code, Mode::Interactive, filename)?;
     let mut ds = DocstringCollector {
         function_infos: Vec::new(),
         class_infos: Vec::new(),
+        style,
     };
-    for stmt in body.iter() {
-        ds.visit_stmt(stmt.clone());
+    if let Some(interactive) = tree_mod.as_interactive() {
+        for stmt in interactive.body.iter() {
+            ds.visit_stmt(stmt.clone());
+        }
     }
-    ds
+    Ok(ds)
 }
 pub struct DocstringCollector {
     pub function_infos: Vec<FunctionInfo>,
     pub class_infos: Vec<ClassInfo>,
+    style: Option<DocstringStyle>,
 }
 
 #[derive(PartialEq, Clone)]
@@ -124,23 +132,23 @@ pub struct ClassInfo {
     pub funcs: Vec<FunctionInfo>,
     pub docstring: Option<Docstring>,
 }
-fn get_docs(expr: &Expr<TextRange>) -> Option<Docstring> {
+fn get_docs(expr: &Expr<TextRange>, style: Option<DocstringStyle>) -> Option<Docstring> {
     if expr.is_constant_expr() {
         let ds = expr.as_constant_expr().unwrap();
         if !ds.clone().value.is_str() {
             return None;
         }
-        let docstring = docstring::parse(ds);
+        let docstring = docstring::parse(ds, style);
         return Some(docstring);
     }
     None
 }
-fn get_func(expr: &FunctionDefKind) -> FunctionInfo {
+fn get_func(expr: &FunctionDefKind, style: Option<DocstringStyle>) -> FunctionInfo {
     let mut function_docs: Option<Docstring> = None;
 
     // Get docstring if the first statement is an Expr
     if let Some(Stmt::Expr(expr_stmt)) = expr.body().first() {
-        function_docs = get_docs(&expr_stmt.value);
+        function_docs = get_docs(&expr_stmt.value, style);
     }
 
     // Walk the function body to collect all return statements
@@ -311,6 +319,136 @@ impl Visitor for ReturnCollector {
     }
 }
 
+/// An attribute of a class, assigned either in the class body or through
+/// `self`/ `cls` in one of its methods.
+#[derive(Clone, Debug)]
+pub struct ClassAttr {
+    pub name: String,
+    pub range: TextRange,
+    pub in_method: bool,
+}
+
+impl ClassAttr {
+    pub fn is_private(&self) -> bool {
+        self.name.starts_with('_')
+    }
+}
+
+/// Returns the attributes an assignment target defines. In the class body
+/// the root name is used (`attr_1.nested = ...` defines `attr_1`), in a
+/// method only attributes set on `self`/ `cls` count.
+pub fn get_target_attrs(target: &Expr, in_method: bool) -> Vec<ClassAttr> {
+    match target {
+        Expr::Tuple(tuple) => tuple
+            .elts
+            .iter()
+            .flat_map(|elt| get_target_attrs(elt, in_method))
+            .collect(),
+        Expr::List(list) => list
+            .elts
+            .iter()
+            .flat_map(|elt| get_target_attrs(elt, in_method))
+            .collect(),
+        Expr::Starred(starred) => get_target_attrs(&starred.value, in_method),
+        Expr::Name(name) if !in_method => vec![ClassAttr {
+            name: name.id.to_string(),
+            range: name.range,
+            in_method,
+        }],
+        Expr::Attribute(attr) if !in_method => get_target_attrs(&attr.value, in_method)
+            .into_iter()
+            .map(|class_attr| ClassAttr {
+                range: attr.range,
+                ..class_attr
+            })
+            .collect(),
+        Expr::Attribute(attr) => match attr.value.as_ref() {
+            Expr::Name(name) if name.id.as_str() == "self" || name.id.as_str() == "cls" => {
+                vec![ClassAttr {
+                    name: attr.attr.to_string(),
+                    range: attr.range,
+                    in_method,
+                }]
+            }
+            _ => get_target_attrs(&attr.value, in_method),
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the attributes a method sets on `self`/ `cls`.
+pub fn get_method_attrs(method: &FunctionDefKind) -> Vec<ClassAttr> {
+    let mut attr_collector = MethodAttrCollector::new();
+    for stmt in method.body() {
+        attr_collector.visit_stmt(stmt.clone());
+    }
+    attr_collector.attrs
+}
+
+/// Collects the `self.x`/ `cls.x` assignments of a method body, ignoring
+/// nested functions and classes.
+struct MethodAttrCollector {
+    pub attrs: Vec<ClassAttr>,
+    func_depth: usize,
+    class_depth: usize,
+}
+
+impl MethodAttrCollector {
+    pub fn new() -> Self {
+        Self {
+            attrs: Vec::new(),
+            func_depth: 0,
+            class_depth: 0,
+        }
+    }
+}
+
+impl Visitor for MethodAttrCollector {
+    fn visit_stmt_function_def(&mut self, node: StmtFunctionDef<TextRange>) {
+        self.func_depth += 1;
+        for stmt in &node.body {
+            self.visit_stmt(stmt.clone());
+        }
+        self.func_depth -= 1;
+    }
+
+    fn visit_stmt_async_function_def(&mut self, node: StmtAsyncFunctionDef<TextRange>) {
+        self.func_depth += 1;
+        for stmt in &node.body {
+            self.visit_stmt(stmt.clone());
+        }
+        self.func_depth -= 1;
+    }
+
+    fn visit_stmt_class_def(&mut self, node: StmtClassDef<TextRange>) {
+        self.class_depth += 1;
+        for stmt in &node.body {
+            self.visit_stmt(stmt.clone());
+        }
+        self.class_depth -= 1;
+    }
+
+    fn visit_stmt_assign(&mut self, node: StmtAssign<TextRange>) {
+        if self.func_depth == 0 && self.class_depth == 0 {
+            for target in &node.targets {
+                self.attrs.extend(get_target_attrs(target, true));
+            }
+        }
+    }
+
+    fn visit_stmt_ann_assign(&mut self, node: StmtAnnAssign<TextRange>) {
+        if self.func_depth == 0 && self.class_depth == 0 {
+            self.attrs.extend(get_target_attrs(&node.target, true));
+        }
+    }
+
+    fn visit_stmt_aug_assign(&mut self, node: StmtAugAssign<TextRange>) {
+        if self.func_depth == 0 && self.class_depth == 0 {
+            self.attrs.extend(get_target_attrs(&node.target, true));
+        }
+    }
+}
+
 //
 // struct ReturnCollector {
 //     pub returns: Vec<StmtReturn>,
@@ -324,7 +462,7 @@ impl Visitor for ReturnCollector {
 
 impl Visitor for DocstringCollector {
     fn visit_stmt_async_function_def(&mut self, node: StmtAsyncFunctionDef<TextRange>) {
-        let function_info = get_func(&FunctionDefKind::Async(node.clone()));
+        let function_info = get_func(&FunctionDefKind::Async(node.clone()), self.style);
         if !self
             .class_infos
             .iter()
@@ -335,7 +473,7 @@ impl Visitor for DocstringCollector {
         self.generic_visit_stmt_async_function_def(node);
     }
     fn visit_stmt_function_def(&mut self, node: StmtFunctionDef<TextRange>) {
-        let function_info = get_func(&FunctionDefKind::Sync(node.clone()));
+        let function_info = get_func(&FunctionDefKind::Sync(node.clone()), self.style);
         if !self
             .class_infos
             .iter()
@@ -352,7 +490,7 @@ impl Visitor for DocstringCollector {
 
         for stmt in &node.body {
             if let Stmt::Expr(expr_stmt) = stmt {
-                let temp_doc = get_docs(&expr_stmt.value);
+                let temp_doc = get_docs(&expr_stmt.value, self.style);
                 if temp_doc.is_some() {
                     // if !temp_doc.clone().unwrap().is_empty() {
                     class_docs = temp_doc;
@@ -360,7 +498,10 @@ impl Visitor for DocstringCollector {
                 }
             }
             if let Stmt::FunctionDef(func_def) = stmt {
-                class_funcs.push(get_func(&FunctionDefKind::Sync(func_def.clone())));
+                class_funcs.push(get_func(
+                    &FunctionDefKind::Sync(func_def.clone()),
+                    self.style,
+                ));
             }
         }
 
@@ -0,0 +1,98 @@
+// the error conversions pyo3 generates for `PyResult` trip clippy
+#![allow(clippy::useless_conversion)]
+
+use crate::config::Config;
+use crate::diagnostic::Diagnostic;
+use crate::docstring::{self, Docstring, DocstringStyle};
+use crate::{files, rule_engine};
+use pyo3::exceptions::{PyFileNotFoundError, PyValueError};
+use pyo3::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// The settings of the `config` file, or of the configuration file found
+/// from `start` like the command line does.
+fn find_config(config: Option<&Path>, start: &Path) -> PyResult<Config> {
+    let path = config
+        .map(Path::to_path_buf)
+        .or_else(|| Config::find(start));
+    load_config(path.as_deref())
+}
+
+/// The settings of the `path` file, the defaults without one.
+fn load_config(path: Option<&Path>) -> PyResult<Config> {
+    match path {
+        Some(path) => Config::load(path).map_err(|errors| {
+            let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+            PyValueError::new_err(errors.join("\n"))
+        }),
+        None => Ok(Config::default()),
+    }
+}
+
+/// Lints the Python source `code`, with the settings of the `config` file
+/// or of the configuration found from `filename`.
+#[pyfunction]
+#[pyo3(signature = (code, filename=None, config=None))]
+pub fn lint_source(
+    py: Python<'_>,
+    code: String,
+    filename: Option<String>,
+    config: Option<PathBuf>,
+) -> PyResult<Vec<Diagnostic>> {
+    let start = Path::new(filename.as_deref().unwrap_or("."));
+    let config = find_config(config.as_deref(), start)?;
+    Ok(
+        py.allow_threads(|| {
+            rule_engine::lint_code_with_config(&code, filename.as_deref(), &config)
+        }),
+    )
+}
+
+/// Lints the files under `paths` in parallel, without holding the GIL,
+/// with the settings of the `config` file or of the configuration file all
+/// the paths share.
+#[pyfunction]
+#[pyo3(signature = (paths, config=None))]
+pub fn lint_paths(
+    py: Python<'_>,
+    paths: Vec<PathBuf>,
+    config: Option<PathBuf>,
+) -> PyResult<Vec<Diagnostic>> {
+    if let Some(missing) = paths.iter().find(|path| !path.exists()) {
+        return Err(PyFileNotFoundError::new_err(format!(
+            "path '{}' does not exist",
+            missing.display()
+        )));
+    }
+    let path = match config {
+        Some(config) => Some(config),
+        None => Config::find_common(&paths).map_err(PyValueError::new_err)?,
+    };
+    let config = load_config(path.as_deref())?;
+    Ok(py.allow_threads(|| {
+        let files = files::collect_files(&paths, &config);
+        rule_engine::lint_files(files, &config)
+            .into_iter()
+            .flat_map(|(_, output)| output)
+            .collect()
+    }))
+}
+
+/// Parses the docstring `text` in `style`, or in the style found from its
+/// sections.
+#[pyfunction]
+#[pyo3(name = "parse", signature = (text, style=None))]
+pub fn parse_docstring(text: &str, style: Option<&str>) -> PyResult<Docstring> {
+    let style = style
+        .map(|name| {
+            DocstringStyle::from_name(name).ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "unknown docstring style '{}', expected one of: {}",
+                    name,
+                    DocstringStyle::NAMES.join(", ")
+                ))
+            })
+        })
+        .transpose()?;
+    Ok(docstring::parse_text(text, style))
+}
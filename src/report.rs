@@ -0,0 +1,215 @@
+use crate::constants::{all_rules, more_info_url};
+use crate::diagnostic::Diagnostic;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::{self, Write};
+
+/// The machine-readable view of a diagnostic written by the JSON reporters.
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    file: Option<&'a str>,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    code: &'a str,
+    message: &'a str,
+    severity: &'a str,
+    symbol: &'a str,
+}
+
+impl<'a> From<&'a Diagnostic> for JsonDiagnostic<'a> {
+    fn from(diagnostic: &'a Diagnostic) -> Self {
+        JsonDiagnostic {
+            file: diagnostic.file.as_deref(),
+            line: diagnostic.line,
+            column: diagnostic.column,
+            end_line: diagnostic.end_line,
+            end_column: diagnostic.end_column,
+            code: &diagnostic.code,
+            message: &diagnostic.message,
+            severity: diagnostic.severity.as_str(),
+            symbol: &diagnostic.symbol,
+        }
+    }
+}
+
+/// Writes every diagnostic as a single pretty-printed JSON array.
+pub fn write_json(out: &mut impl Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
+    let json_diagnostics: Vec<JsonDiagnostic> = diagnostics.iter().map(Into::into).collect();
+    serde_json::to_writer_pretty(&mut *out, &json_diagnostics)?;
+    writeln!(out)
+}
+
+/// Writes one compact JSON object per line (JSON Lines).
+pub fn write_jsonl(out: &mut impl Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
+    for diagnostic in diagnostics {
+        serde_json::to_writer(&mut *out, &JsonDiagnostic::from(diagnostic))?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Writes a SARIF 2.1.0 log with a single run, as accepted by GitHub code
+/// scanning. SARIF columns are 1-based, so the 0-based columns of the
+/// diagnostics are shifted by one.
+pub fn write_sarif(out: &mut impl Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
+    let rules = all_rules();
+    let sarif_rules: Vec<Value> = rules
+        .iter()
+        .map(|(code, description)| {
+            json!({
+                "id": code,
+                "shortDescription": { "text": description },
+                "helpUri": more_info_url(code),
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut result = json!({
+                "ruleId": diagnostic.code,
+                "level": diagnostic.severity.as_str(),
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": sarif_uri(diagnostic.file.as_deref().unwrap_or("<embedded>")),
+                        },
+                        "region": {
+                            "startLine": diagnostic.line,
+                            "startColumn": diagnostic.column + 1,
+                            "endLine": diagnostic.end_line,
+                            "endColumn": diagnostic.end_column + 1,
+                        },
+                    },
+                }],
+            });
+            if let Some(index) = rules.iter().position(|(code, _)| code == &diagnostic.code) {
+                result["ruleIndex"] = json!(index);
+            }
+            result
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "vipyrdocs",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": sarif_rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_writer_pretty(&mut *out, &log)?;
+    writeln!(out)
+}
+
+/// SARIF wants URIs, so paths use forward slashes and drop a leading `./`.
+fn sarif_uri(path: &str) -> String {
+    let uri = path.replace('\\', "/");
+    uri.strip_prefix("./").unwrap_or(&uri).to_string()
+}
+
+////////// Tests
+
+#[cfg(test)]
+fn sample_diagnostics() -> Vec<Diagnostic> {
+    vec![
+        Diagnostic::new(
+            "D010".to_string(),
+            "D010 docstring should be defined".to_string(),
+            "function_1",
+            (2, 0),
+            (3, 8),
+        )
+        .with_file(Some("src/module.py")),
+        Diagnostic::new(
+            "D030".to_string(),
+            "D030 \"quoted\" message".to_string(),
+            "Class1",
+            (7, 4),
+            (7, 12),
+        ),
+    ]
+}
+
+#[test]
+pub fn test_write_jsonl() {
+    let mut out: Vec<u8> = Vec::new();
+    write_jsonl(&mut out, &sample_diagnostics()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        r#"{"file":"src/module.py","line":2,"column":0,"end_line":3,"end_column":8,"code":"D010","message":"D010 docstring should be defined","severity":"error","symbol":"function_1"}"#
+    );
+    assert_eq!(
+        lines[1],
+        r#"{"file":null,"line":7,"column":4,"end_line":7,"end_column":12,"code":"D030","message":"D030 \"quoted\" message","severity":"error","symbol":"Class1"}"#
+    );
+}
+
+#[test]
+pub fn test_write_json() {
+    let mut out: Vec<u8> = Vec::new();
+    write_json(&mut out, &sample_diagnostics()).unwrap();
+
+    let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let parsed = parsed.as_array().unwrap();
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0]["file"], "src/module.py");
+    assert_eq!(parsed[0]["code"], "D010");
+    assert_eq!(parsed[0]["symbol"], "function_1");
+    assert_eq!(parsed[1]["line"], 7);
+    assert_eq!(parsed[1]["column"], 4);
+
+    let mut out: Vec<u8> = Vec::new();
+    write_json(&mut out, &[]).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "[]\n");
+}
+
+#[test]
+pub fn test_write_sarif() {
+    let mut out: Vec<u8> = Vec::new();
+    write_sarif(&mut out, &sample_diagnostics()).unwrap();
+    let log: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["$schema"], SARIF_SCHEMA);
+    let run = &log["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), all_rules().len());
+    assert_eq!(rules[0]["id"], "D010");
+    assert_eq!(rules[0]["helpUri"], "https://example.com/d010");
+    assert!(rules[0]["shortDescription"]["text"].is_string());
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "D010");
+    assert_eq!(results[0]["ruleIndex"], 0);
+    assert_eq!(results[0]["level"], "error");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/module.py");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 1);
+    assert_eq!(location["region"]["endLine"], 3);
+    assert_eq!(location["region"]["endColumn"], 9);
+}
+
+#[test]
+pub fn test_sarif_uri() {
+    assert_eq!(sarif_uri("./src/module.py"), "src/module.py");
+    assert_eq!(sarif_uri("src\\module.py"), "src/module.py");
+}
@@ -1 +1,73 @@
+from collections.abc import Sequence
+from os import PathLike
+
 def hello_from_bin() -> str: ...
+def apply_rules(code: str, file_name: str | None = None) -> list[str]: ...
+
+class Diagnostic:
+    file: str | None
+    line: int
+    column: int
+    end_line: int
+    end_column: int
+    code: str
+    message: str
+    severity: str
+    symbol: str
+    fixable: bool
+
+def lint_source(
+    code: str,
+    filename: str | None = None,
+    config: str | PathLike[str] | None = None,
+) -> list[Diagnostic]: ...
+def lint_paths(
+    paths: list[str | PathLike[str]],
+    config: str | PathLike[str] | None = None,
+) -> list[Diagnostic]: ...
+
+class docstring:
+    """The docstring parser the rules use."""
+
+    class Entry:
+        names: list[str]
+        name: str | None
+        annotation: str | None
+        description: str
+        line_offset: int
+        end_line_offset: int
+
+    class Section:
+        name: str | None
+        kind: str | None
+        description: str
+        entries: list[docstring.Entry]
+        line_offset: int
+        end_line_offset: int
+
+    class Docstring:
+        args: list[str] | None
+        args_sections: list[str] | None
+        attrs: list[str] | None
+        attrs_sections: list[str] | None
+        returns: list[docstring.Entry] | None
+        returns_sections: list[str] | None
+        yields: list[docstring.Entry] | None
+        yields_sections: list[str] | None
+        raises: list[str] | None
+        raises_sections: list[str] | None
+        sections: list[docstring.Section]
+        def __init__(
+            self,
+            args: Sequence[str] | None = None,
+            args_sections: Sequence[str] | None = None,
+            attrs: Sequence[str] | None = None,
+            attrs_sections: Sequence[str] | None = None,
+            returns_sections: Sequence[str] | None = None,
+            yields_sections: Sequence[str] | None = None,
+            raises: Sequence[str] | None = None,
+            raises_sections: Sequence[str] | None = None,
+        ) -> None: ...
+
+    @staticmethod
+    def parse(text: str, style: str | None = None) -> docstring.Docstring: ...
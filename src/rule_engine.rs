@@ -1,65 +1,514 @@
+use crate::config::Config;
 use crate::constants::{
-    arg_in_docstr_msg, arg_not_in_docstr_msg, args_section_in_docstr_msg,
-    args_section_not_in_docstr_msg, docstr_missing_msg, duplicate_arg_msg, duplicate_exc_msg,
-    exc_in_docstr_msg, exc_not_in_docstr_msg, mult_args_sections_in_docstr_msg,
-    mult_raises_sections_in_docstr_msg, mult_returns_sections_in_docstr_msg,
-    mult_yields_sections_in_docstr_msg, raises_section_in_docstr_msg,
-    raises_section_not_in_docstr_msg, re_raise_no_exc_in_docstr_msg, returns_section_in_docstr_msg,
-    returns_section_not_in_docstr_msg, yields_section_in_docstr_msg,
+    all_rules, arg_in_docstr_code, arg_in_docstr_msg, arg_not_in_docstr_code,
+    arg_not_in_docstr_msg, args_section_in_docstr_code, args_section_in_docstr_msg,
+    args_section_not_in_docstr_code, args_section_not_in_docstr_msg, attr_in_docstr_code,
+    attr_in_docstr_msg, attr_not_in_docstr_code, attr_not_in_docstr_msg,
+    attrs_section_in_docstr_code, attrs_section_in_docstr_msg, attrs_section_not_in_docstr_code,
+    attrs_section_not_in_docstr_msg, check_error_msg, docstr_missing_code, docstr_missing_msg,
+    duplicate_arg_in_docstr_code, duplicate_arg_msg, duplicate_attr_in_docstr_code,
+    duplicate_attr_msg, duplicate_exc_code, duplicate_exc_msg, exc_in_docstr_code,
+    exc_in_docstr_msg, exc_not_in_docstr_code, exc_not_in_docstr_msg,
+    mult_args_sections_in_docstr_code, mult_args_sections_in_docstr_msg,
+    mult_attrs_sections_in_docstr_code, mult_attrs_sections_in_docstr_msg,
+    mult_raises_sections_in_docstr_code, mult_raises_sections_in_docstr_msg,
+    mult_returns_sections_in_docstr_code, mult_returns_sections_in_docstr_msg,
+    mult_yields_sections_in_docstr_code, mult_yields_sections_in_docstr_msg, parse_error_code,
+    parse_error_msg, raises_section_in_docstr_code, raises_section_in_docstr_msg,
+    raises_section_not_in_docstr_code, raises_section_not_in_docstr_msg,
+    re_raise_no_exc_in_docstr_code, re_raise_no_exc_in_docstr_msg, read_error_code, read_error_msg,
+    returns_section_in_docstr_code, returns_section_in_docstr_msg,
+    returns_section_not_in_docstr_code, returns_section_not_in_docstr_msg,
+    unknown_code_in_noqa_code, unknown_code_in_noqa_msg, unused_noqa_code, unused_noqa_msg,
+    yields_section_in_docstr_code, yields_section_in_docstr_msg, yields_section_not_in_docstr_code,
     yields_section_not_in_docstr_msg,
 };
-use crate::plugin::{get_result, DocstringCollector, FunctionDefKind, FunctionInfo, YieldKind};
+use crate::diagnostic::Diagnostic;
+use crate::docstring::{section_spans, Docstring, SectionSpan};
+use crate::fix::{attach_fixes, docstring_text};
+use crate::noqa::{
+    line_and_column, parse_docstring_noqa, parse_noqa_comments, signature_end_line, NoqaComments,
+    NoqaDirective,
+};
+use crate::plugin::{
+    get_method_attrs, get_result, get_target_attrs, ClassAttr, ClassInfo, DocstringCollector,
+    FunctionDefKind, FunctionInfo, YieldKind,
+};
+use crate::source::decode_source;
 use pyo3::prelude::*;
+use rayon::prelude::*;
 use rustpython_ast::text_size::TextRange;
-use rustpython_ast::{Arguments, Expr, ExprAttribute, ExprCall, StmtRaise, StmtReturn};
+use rustpython_ast::{
+    Arguments, Expr, ExprAttribute, ExprCall, Ranged, Stmt, StmtRaise, StmtReturn,
+};
 use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::ops::Range;
 
-fn read_file(file_name: &str) -> String {
-    // Read the file and return the contents
-    fs::read_to_string(file_name).unwrap_or_default()
+fn is_test_file(file_name: Option<&str>, config: &Config) -> bool {
+    file_name.is_some_and(|file_name| config.is_test_file(file_name))
 }
 
-fn is_test_file(file_name: Option<&str>) -> bool {
-    if file_name.is_some() {
-        let file_name = file_name.unwrap().split('/').next_back().unwrap();
+#[allow(dead_code)]
+pub fn lint_file(code: &str, file_name: Option<&str>) -> Vec<Diagnostic> {
+    lint_file_with_config(code, file_name, &Config::default())
+}
 
-        if file_name.starts_with("test_") || file_name.starts_with("conftest.py") {
-            return true;
-        }
+/// Like [`lint_file`], with the rule selection and test file patterns taken
+/// from `config`.
+pub fn lint_file_with_config(
+    code: &str,
+    file_name: Option<&str>,
+    config: &Config,
+) -> Vec<Diagnostic> {
+    let Some(file) = file_name else {
+        return lint_code_with_config(code, file_name, config);
+    };
+
+    match fs::read(file) {
+        Ok(bytes) => lint_bytes_with_config(&bytes, file_name, config),
+        Err(error) => read_error_output(error.to_string().as_str(), file_name, config),
     }
-    false
 }
 
-pub fn lint_file(code: &str, file_name: Option<&str>) -> Vec<String> {
-    // Make a mutable String to hold the actual code
-    let mut code = code.to_string();
+/// Decodes `bytes` like the interpreter would, honoring a PEP 263 coding
+/// declaration, and lints the result.
+pub fn lint_bytes_with_config(
+    bytes: &[u8],
+    file_name: Option<&str>,
+    config: &Config,
+) -> Vec<Diagnostic> {
+    match decode_source(bytes) {
+        Ok(code) => lint_code_with_config(code.as_str(), file_name, config),
+        Err(error) => read_error_output(error.as_str(), file_name, config),
+    }
+}
 
-    // If there's a file, override it
-    if let Some(file) = file_name {
-        code = read_file(file); // assuming this returns String
+/// DCO004: an unreadable file is reported instead of linted as empty.
+pub fn read_error_output(error: &str, file_name: Option<&str>, config: &Config) -> Vec<Diagnostic> {
+    if !is_enabled_for_file(config, file_name, read_error_code().as_str()) {
+        return Vec::new();
     }
+    vec![
+        Diagnostic::new(read_error_code(), read_error_msg(error), "", (1, 0), (1, 0))
+            .with_file(file_name),
+    ]
+}
 
-    apply_rules(code.as_str(), file_name)
+/// Lints `files` in parallel on the current rayon thread pool and returns
+/// the problems of each file, sorted by path.
+pub fn lint_files(mut files: Vec<String>, config: &Config) -> Vec<(String, Vec<Diagnostic>)> {
+    files.sort();
+    files.dedup();
+    files
+        .into_par_iter()
+        .map(|file| {
+            let output =
+                catch_check_panic(|| lint_file_with_config("", Some(file.as_str()), config))
+                    .unwrap_or_else(|reason| {
+                        check_error_output(&reason, Some(file.as_str()), config)
+                    });
+            (file, output)
+        })
+        .collect()
 }
 
-#[pyfunction]
-#[pyo3(signature = (code, file_name=None))]
-pub fn apply_rules(code: &str, file_name: Option<&str>) -> Vec<String> {
-    let mut output: Vec<String> = Vec::new();
+/// Runs the check of one file, returning the reason if it panicked, so a bug
+/// in a rule costs that file its results rather than the whole run.
+pub fn catch_check_panic<T>(check: impl FnOnce() -> T) -> Result<T, String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(check)).map_err(|panic| {
+        panic
+            .downcast_ref::<&str>()
+            .map(|reason| reason.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default()
+    })
+}
+
+/// DCO004: a file whose check panicked is reported as not checked.
+pub fn check_error_output(
+    reason: &str,
+    file_name: Option<&str>,
+    config: &Config,
+) -> Vec<Diagnostic> {
+    if !is_enabled_for_file(config, file_name, read_error_code().as_str()) {
+        return Vec::new();
+    }
+    vec![Diagnostic::new(
+        read_error_code(),
+        check_error_msg(reason),
+        "",
+        (1, 0),
+        (1, 0),
+    )
+    .with_file(file_name)]
+}
+
+/// Runs every rule against `code` and returns the problems found, tagged
+/// with `file_name` when one is given.
+pub fn lint_code(code: &str, file_name: Option<&str>) -> Vec<Diagnostic> {
+    lint_code_with_config(code, file_name, &Config::default())
+}
+
+/// Like [`lint_code`], skipping the rules that `config` disables for the
+/// file.
+pub fn lint_code_with_config(
+    code: &str,
+    file_name: Option<&str>,
+    config: &Config,
+) -> Vec<Diagnostic> {
+    let mut output: Vec<Diagnostic> = Vec::new();
+
+    let noqa = parse_noqa_comments(code);
+    // `# vipyrdocs: noqa` skips the whole module
+    if noqa.file_level {
+        return output;
+    }
+
+    let things = match get_result(code, file_name, config.convention) {
+        Ok(things) => things,
+        Err(error) => {
+            // DC001: the other checks need the syntax tree
+            if is_enabled_for_file(config, file_name, parse_error_code().as_str()) {
+                let position = line_and_column(code, error.offset.to_usize());
+                output.push(Diagnostic::new(
+                    parse_error_code(),
+                    parse_error_msg(error.error.to_string().as_str()),
+                    "",
+                    position,
+                    position,
+                ));
+            }
+            return output
+                .into_iter()
+                .map(|diagnostic| diagnostic.with_file(file_name))
+                .collect();
+        }
+    };
+
+    let test_file = is_test_file(file_name, config);
 
-    let things = get_result(code, file_name);
+    output.extend(generate_rules_output(code, &things, test_file, |code| {
+        is_enabled_for_file(config, file_name, code)
+    }));
 
-    let test_file = is_test_file(file_name);
+    let scopes = get_noqa_scopes(code, &things, &noqa);
+    let mut used_noqa: HashSet<(usize, usize, Option<String>)> = HashSet::new();
 
-    output.extend(generate_rules_output(code, &things, test_file));
+    output.retain(|diagnostic| !is_suppressed(diagnostic, &scopes, &mut used_noqa));
+    attach_fixes(code, &things, config.convention, &mut output);
+
+    // DC002/ DC003: unused noqa directives and unknown codes in them
+    output.extend(check_noqa_directives(
+        &noqa, &scopes, &used_noqa, config, file_name,
+    ));
 
     // apply the rules
     output
+        .into_iter()
+        .map(|diagnostic| diagnostic.with_file(file_name))
+        .collect()
+}
+
+/// Whether the rule `code` runs for `file_name`, taking `per-file-ignores`
+/// into account.
+fn is_enabled_for_file(config: &Config, file_name: Option<&str>, code: &str) -> bool {
+    config.is_enabled(code) && file_name.is_none_or(|file| !config.is_ignored_for_file(file, code))
+}
+
+/// A function/ method/ class with a `# noqa` comment on its `def`/ `class`
+/// line or on the first line of its docstring.
+struct NoqaScope<'a> {
+    symbol: &'a str,
+    start_line: usize,
+    end_line: usize,
+    directives: Vec<NoqaDirective>,
+}
+
+fn noqa_scope<'a>(
+    code: &str,
+    noqa: &NoqaComments,
+    symbol: &'a str,
+    range: &TextRange,
+    body: &[Stmt],
+    docstring: Option<&Docstring>,
+) -> Option<NoqaScope<'a>> {
+    let (start_line, _) = line_and_column(code, range.start().to_usize());
+    // any line of a signature split over several lines
+    let body_start = body
+        .first()
+        .map_or(range.end(), |stmt| stmt.range().start());
+    let signature_end = signature_end_line(code, range.start().to_usize(), body_start.to_usize());
+    let mut directives: Vec<NoqaDirective> = (start_line..=signature_end)
+        .flat_map(|line| noqa.on_line(line).cloned())
+        .collect();
+    if let Some(docstring) = docstring {
+        let docstring_start = docstring.get_range().start().to_usize();
+        let (docstring_line, _) = line_and_column(code, docstring_start);
+        if docstring_line > signature_end {
+            directives.extend(noqa.on_line(docstring_line).cloned());
+            directives.extend(parse_docstring_noqa(code, docstring_start));
+        }
+    }
+    if directives.is_empty() {
+        return None;
+    }
+    let (end_line, _) = find_range_end(code, range);
+    Some(NoqaScope {
+        symbol,
+        start_line,
+        end_line,
+        directives,
+    })
+}
+
+fn get_noqa_scopes<'a>(
+    code: &str,
+    things: &'a DocstringCollector,
+    noqa: &NoqaComments,
+) -> Vec<NoqaScope<'a>> {
+    let mut scopes = Vec::new();
+    let functions = things.function_infos.iter().chain(
+        things
+            .class_infos
+            .iter()
+            .flat_map(|class| class.funcs.iter()),
+    );
+    for function in functions {
+        scopes.extend(noqa_scope(
+            code,
+            noqa,
+            function.def.name(),
+            function.def.range(),
+            function.def.body(),
+            function.docstring.as_ref(),
+        ));
+    }
+    for class_info in &things.class_infos {
+        scopes.extend(noqa_scope(
+            code,
+            noqa,
+            class_info.def.name.as_str(),
+            &class_info.def.range,
+            &class_info.def.body,
+            class_info.docstring.as_ref(),
+        ));
+    }
+    scopes
+}
+
+/// The name and 1-based first and last line of every function, method and
+/// class in `code`, empty when it does not parse.
+pub fn definition_ranges(code: &str) -> Vec<(String, usize, usize)> {
+    let Ok(things) = get_result(code, None, None) else {
+        return Vec::new();
+    };
+    let line_range = |range: &TextRange| {
+        let (start_line, _) = line_and_column(code, range.start().to_usize());
+        let (end_line, _) = find_range_end(code, range);
+        (start_line, end_line)
+    };
+    let functions = things.function_infos.iter().chain(
+        things
+            .class_infos
+            .iter()
+            .flat_map(|class| class.funcs.iter()),
+    );
+    let mut ranges = Vec::new();
+    for function in functions {
+        let (start_line, end_line) = line_range(function.def.range());
+        ranges.push((function.def.name().to_string(), start_line, end_line));
+    }
+    for class_info in &things.class_infos {
+        let (start_line, end_line) = line_range(&class_info.def.range);
+        ranges.push((class_info.def.name.to_string(), start_line, end_line));
+    }
+    ranges
+}
+
+/// Like [`definition_ranges`] for the file at `file_name`, empty when it
+/// can not be read.
+pub fn file_definition_ranges(file_name: &str) -> Vec<(String, usize, usize)> {
+    fs::read(file_name)
+        .ok()
+        .and_then(|bytes| decode_source(&bytes).ok())
+        .map(|code| definition_ranges(&code))
+        .unwrap_or_default()
+}
+
+/// The innermost of the [`definition_ranges`] named like the symbol of
+/// `diagnostic` that contains it.
+pub fn definition_of<'a>(
+    definitions: &'a [(String, usize, usize)],
+    diagnostic: &Diagnostic,
+) -> Option<&'a (String, usize, usize)> {
+    definitions
+        .iter()
+        .filter(|(symbol, start, end)| {
+            *symbol == diagnostic.symbol && *start <= diagnostic.line && diagnostic.line <= *end
+        })
+        .min_by_key(|(_, start, end)| end - start)
+}
+
+/// The symbol of `diagnostic` prefixed with its enclosing classes, e.g.
+/// `Class1.method_1`.
+pub fn qualified_symbol(definitions: &[(String, usize, usize)], diagnostic: &Diagnostic) -> String {
+    let Some(definition) = definition_of(definitions, diagnostic) else {
+        return diagnostic.symbol.clone();
+    };
+    let mut parents: Vec<&(String, usize, usize)> = definitions
+        .iter()
+        .filter(|parent| {
+            *parent != definition && parent.1 <= definition.1 && definition.2 <= parent.2
+        })
+        .collect();
+    // outermost first
+    parents.sort_by_key(|(_, start, end)| std::cmp::Reverse(end - start));
+    parents
+        .iter()
+        .map(|(name, _, _)| name.as_str())
+        .chain([definition.0.as_str()])
+        .collect::<Vec<&str>>()
+        .join(".")
+}
+
+/// Whether a `# noqa` of the diagnostic's symbol suppresses it, the
+/// directives (and codes) that did are added to `used`.
+fn is_suppressed(
+    diagnostic: &Diagnostic,
+    scopes: &[NoqaScope],
+    used: &mut HashSet<(usize, usize, Option<String>)>,
+) -> bool {
+    let mut suppressed = false;
+    for scope in scopes {
+        if scope.symbol != diagnostic.symbol
+            || !(scope.start_line..=scope.end_line).contains(&diagnostic.line)
+        {
+            continue;
+        }
+        for directive in &scope.directives {
+            match &directive.codes {
+                Some(codes) => {
+                    for noqa_code in codes {
+                        if diagnostic.code.starts_with(noqa_code.as_str()) {
+                            used.insert((
+                                directive.line,
+                                directive.column,
+                                Some(noqa_code.clone()),
+                            ));
+                            suppressed = true;
+                        }
+                    }
+                }
+                None => {
+                    used.insert((directive.line, directive.column, None));
+                    suppressed = true;
+                }
+            }
+        }
+    }
+    suppressed
+}
+
+fn check_noqa_directives(
+    noqa: &NoqaComments,
+    scopes: &[NoqaScope],
+    used: &HashSet<(usize, usize, Option<String>)>,
+    config: &Config,
+    file_name: Option<&str>,
+) -> Vec<Diagnostic> {
+    let is_enabled = |code: &str| is_enabled_for_file(config, file_name, code);
+    let check_unused = is_enabled(unused_noqa_code().as_str());
+    let check_unknown = is_enabled(unknown_code_in_noqa_code().as_str());
+    if !check_unused && !check_unknown {
+        return Vec::new();
+    }
+
+    // directives on the first line of a docstring only live in the scopes
+    let mut directives: Vec<&NoqaDirective> = noqa.directives.iter().collect();
+    for directive in scopes.iter().flat_map(|scope| scope.directives.iter()) {
+        if !directives.contains(&directive) {
+            directives.push(directive);
+        }
+    }
+    directives.sort_by_key(|directive| (directive.line, directive.column));
+
+    let rules = all_rules();
+    let mut problems: Vec<Diagnostic> = Vec::new();
+    for directive in directives {
+        let start = (directive.line, directive.column);
+        let end = (directive.line, directive.end_column);
+        let Some(codes) = &directive.codes else {
+            // away from a definition a blanket `# noqa` is for other tools
+            let in_scope = scopes
+                .iter()
+                .any(|scope| scope.directives.contains(directive));
+            if check_unused && in_scope && !used.contains(&(directive.line, directive.column, None))
+            {
+                problems.push(Diagnostic::new(
+                    unused_noqa_code(),
+                    unused_noqa_msg(None),
+                    "",
+                    start,
+                    end,
+                ));
+            }
+            continue;
+        };
+        let mut unused: Vec<&str> = Vec::new();
+        for noqa_code in codes {
+            let known_rules: Vec<&String> = rules
+                .iter()
+                .map(|(rule, _)| rule)
+                .filter(|rule| rule.starts_with(noqa_code.as_str()))
+                .collect();
+            if known_rules.is_empty() {
+                if check_unknown {
+                    problems.push(Diagnostic::new(
+                        unknown_code_in_noqa_code(),
+                        unknown_code_in_noqa_msg(noqa_code),
+                        "",
+                        start,
+                        end,
+                    ));
+                }
+                continue;
+            }
+            // a code for a rule that is turned off could not suppress anything
+            let any_enabled = known_rules.iter().any(|rule| is_enabled(rule));
+            if any_enabled
+                && !used.contains(&(directive.line, directive.column, Some(noqa_code.clone())))
+            {
+                unused.push(noqa_code);
+            }
+        }
+        if check_unused && !unused.is_empty() {
+            problems.push(Diagnostic::new(
+                unused_noqa_code(),
+                unused_noqa_msg(Some(unused.join(",").as_str())),
+                "",
+                start,
+                end,
+            ));
+        }
+    }
+    problems
+}
+
+/// Python entry point, renders every diagnostic as `line:column message`.
+#[pyfunction]
+#[pyo3(signature = (code, file_name=None))]
+#[allow(dead_code)]
+pub fn apply_rules(code: &str, file_name: Option<&str>) -> Vec<String> {
+    lint_code(code, file_name)
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect()
 }
 
 /// Finds the (line, column) of `target_string` if it exists within the specified TextRange of `s`.
-/// Returns (line_number, column_number) on success. Both are 1-based.
+/// Returns (line_number, column_number) on success. Both are 1-based. The
+/// targets match regardless of ASCII case, columns count characters.
 pub fn find_string_in_text_range(
     s: &str,
     range: &TextRange,
@@ -68,84 +517,78 @@ pub fn find_string_in_text_range(
     let start = usize::try_from(range.start().to_u32()).unwrap();
     let end = usize::try_from(range.end().to_u32()).unwrap();
 
-    let sub = &s[start..end].to_lowercase();
+    let sub = &s[start..end];
     let mut positions: Vec<(usize, usize, String)> = Vec::new();
-    let target_strings_lower: Vec<String> =
-        target_strings.iter().map(|t| t.to_lowercase()).collect();
 
     let mut offset = 0;
     while offset < sub.len() {
-        let mut matched = false;
-        for (i, target) in target_strings_lower.iter().enumerate() {
-            if sub[offset..].starts_with(target) {
+        let rest = &sub.as_bytes()[offset..];
+        // a match ends on a character boundary as the bytes only differ in
+        // ASCII case
+        let matched = target_strings.iter().find(|target| {
+            rest.len() >= target.len()
+                && rest[..target.len()].eq_ignore_ascii_case(target.as_bytes())
+        });
+        match matched {
+            Some(target) => {
                 let absolute_pos = start + offset;
-
-                let before = &s[..absolute_pos];
-                let line_number = before.lines().count(); // 1-based
-
-                let column_number = before
-                    .rfind('\n')
-                    .map(|idx| absolute_pos - idx - 1)
-                    .unwrap_or(absolute_pos);
-
+                let (line_number, column_number) = line_and_char_column(s, absolute_pos);
                 positions.push((
-                    line_number - 2,
+                    line_number.saturating_sub(2),
                     column_number,
-                    target_strings[i].to_string(),
+                    target.to_string(),
                 ));
                 offset += target.len();
-                matched = true;
-                break; // only take the first match at this position
             }
-        }
-        if !matched {
-            offset += 1;
+            None => offset += sub[offset..].chars().next().map_or(1, char::len_utf8),
         }
     }
 
     if positions.is_empty() {
-        let before = &s[..start];
-        let line_number = before.lines().count(); // 1-based
-
-        let column_number = before
-            .rfind('\n')
-            .map(|idx| start - idx - 1)
-            .unwrap_or(start);
-
-        positions.push((line_number - 2, column_number, "".to_string()));
+        let (line_number, column_number) = line_and_char_column(s, start);
+        positions.push((line_number.saturating_sub(2), column_number, "".to_string()));
     }
 
     positions
 }
 
-fn find_line_and_column(s: &str, char_index: usize) -> Option<(usize, usize)> {
-    let mut current_char_index = 0;
-
-    for (line_number, line) in s.lines().enumerate() {
-        let line_char_count = line.chars().count();
-        let next_char_index = current_char_index + line_char_count;
-
-        if char_index < next_char_index {
-            let column = char_index - current_char_index;
-            return Some((line_number + 1, column)); // Lines are 1-based, columns 0-based
-        }
-
-        // +1 to account for the newline character (if there was one)
-        current_char_index = next_char_index + 1;
-    }
+/// The number of lines before the byte `offset` of `s`, counted like
+/// `str::lines` does, and the column of `offset` in characters.
+fn line_and_char_column(s: &str, offset: usize) -> (usize, usize) {
+    let before = &s[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (before.lines().count(), before[line_start..].chars().count())
+}
 
-    None
+/// Returns the 1-based line and the 0-based character column of the byte
+/// `offset` of `s`, or `None` if `offset` is not a character boundary of `s`.
+fn find_line_and_column(s: &str, offset: usize) -> Option<(usize, usize)> {
+    let before = s.get(..offset)?;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Some((
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count(),
+    ))
 }
 
-fn format_problem(line: usize, line_location: usize, error_msg: String) -> String {
-    format!("{}:{} {}", line, line_location, error_msg)
+/// Returns the (line, column) just past the end of `range`, in the same
+/// convention as `find_line_and_column`.
+fn find_range_end(s: &str, range: &TextRange) -> (usize, usize) {
+    let end = range.end().to_usize().min(s.len());
+    let before = &s[..end];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map(|idx| before[idx + 1..].chars().count())
+        .unwrap_or(before.chars().count());
+    (line, column)
 }
 fn check_functions_for_duplicate_arg_in_args_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -167,11 +610,9 @@ fn check_functions_for_duplicate_arg_in_args_section(
         let docstring_args_sections = function.docstring.clone().unwrap().get_args_sections();
         let docstring_args = function.docstring.clone().unwrap().get_args();
 
-        println!("{}", function.docstring.clone().unwrap().__repr__());
         if docstring_args_sections.is_empty() {
             continue;
         }
-        println!("docstring_args: {:?}", docstring_args);
         let mut counts = HashMap::new();
 
         let mut _range = function
@@ -188,11 +629,13 @@ fn check_functions_for_duplicate_arg_in_args_section(
                     &_range,
                     vec!["args", "arguments", "parameters"],
                 );
-                let (line, line_location, _) = args_lines.first().unwrap().to_owned();
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
+                let (line, line_location, found) = args_lines.first().unwrap().to_owned();
+                problem_functions.push(Diagnostic::new(
+                    duplicate_arg_in_docstr_code(),
                     duplicate_arg_msg(arg_name.as_str()),
+                    function.def.name(),
+                    (line, line_location),
+                    (line, line_location + found.chars().count()),
                 ));
             }
         }
@@ -202,11 +645,11 @@ fn check_functions_for_duplicate_arg_in_args_section(
 }
 
 fn check_functions_for_extra_arg_in_args_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -228,12 +671,9 @@ fn check_functions_for_extra_arg_in_args_section(
         let docstring_args_sections = function.docstring.clone().unwrap().get_args_sections();
         let docstring_args = function.docstring.clone().unwrap().get_args();
 
-        println!("{}", function.docstring.clone().unwrap().__repr__());
         if docstring_args_sections.is_empty() {
             continue;
         }
-        println!("docstring_args: {:?}", docstring_args);
-        println!("clean_args: {:?}", clean_args);
         let mut _range = function.def.range();
         // if DC022 is here we don't need to check for DC023
         if function
@@ -248,16 +688,16 @@ fn check_functions_for_extra_arg_in_args_section(
         }
 
         let mut arg_names: Vec<String> = Vec::new();
-        if clean_args.vararg.is_some() {
-            arg_names.push(clean_args.vararg.unwrap().arg.to_string());
+        if let Some(vararg) = clean_args.vararg {
+            arg_names.push(vararg.arg.to_string());
             // if let Some(_result) =
             //     is_arg_in_docstring(arg_name, &docstring_args, _range, file_contents)
             // {
             //     problem_functions.push(_result);
             // }
         }
-        if clean_args.kwarg.is_some() {
-            arg_names.push(clean_args.kwarg.unwrap().arg.to_string());
+        if let Some(kwarg) = clean_args.kwarg {
+            arg_names.push(kwarg.arg.to_string());
         }
         for arg in clean_args.args {
             arg_names.push(arg.def.arg.to_string());
@@ -272,11 +712,13 @@ fn check_functions_for_extra_arg_in_args_section(
             if !arg_names.contains(&arg_name) {
                 let args_lines =
                     find_string_in_text_range(file_contents, _range, vec![arg_name.as_str()]);
-                let (line, line_location, _) = args_lines.first().unwrap().to_owned();
-                problem_functions.push(format_problem(
-                    line + 2,
-                    line_location,
+                let (line, line_location, found) = args_lines.first().unwrap().to_owned();
+                problem_functions.push(Diagnostic::new(
+                    arg_in_docstr_code(),
                     arg_in_docstr_msg(arg_name.as_str()),
+                    function.def.name(),
+                    (line + 2, line_location),
+                    (line + 2, line_location + found.chars().count()),
                 ));
             }
         }
@@ -285,11 +727,11 @@ fn check_functions_for_extra_arg_in_args_section(
     problem_functions
 }
 fn check_functions_for_multiple_exc_in_raises_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -315,11 +757,13 @@ fn check_functions_for_multiple_exc_in_raises_section(
                 &_docstring.get_range(),
                 vec!["Raise", "Raises"],
             );
-            let (line, line_location, _) = exc_lines.first().unwrap().to_owned();
-            problem_functions.push(format_problem(
-                line,
-                line_location,
+            let (line, line_location, found) = exc_lines.first().unwrap().to_owned();
+            problem_functions.push(Diagnostic::new(
+                duplicate_exc_code(),
                 duplicate_exc_msg(raise.as_str()),
+                function.def.name(),
+                (line, line_location),
+                (line, line_location + found.chars().count()),
             ));
         }
     }
@@ -345,11 +789,11 @@ fn find_duplicates(strings: &Vec<String>) -> Vec<String> {
 }
 
 fn check_functions_for_re_raise_no_exc_in_raises_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -379,11 +823,13 @@ fn check_functions_for_re_raise_no_exc_in_raises_section(
         if is_reraise && docstring_raises.is_empty() {
             let exc_lines =
                 find_string_in_text_range(file_contents, &_docstring.get_range(), vec!["raise"]);
-            let (line, line_location, _) = exc_lines.first().unwrap().to_owned();
-            problem_functions.push(format_problem(
-                line,
-                line_location,
+            let (line, line_location, found) = exc_lines.first().unwrap().to_owned();
+            problem_functions.push(Diagnostic::new(
+                re_raise_no_exc_in_docstr_code(),
                 re_raise_no_exc_in_docstr_msg(),
+                function.def.name(),
+                (line, line_location),
+                (line, line_location + found.chars().count()),
             ));
         }
     }
@@ -392,11 +838,11 @@ fn check_functions_for_re_raise_no_exc_in_raises_section(
 }
 
 fn check_functions_for_extra_exc_in_raises_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -441,15 +887,13 @@ fn check_functions_for_extra_exc_in_raises_section(
         }
         for exc_name in docstring_raises {
             if !exc_names.contains(&exc_name) {
-                let exc_lines = find_string_in_text_range(
+                problem_functions.push(entry_problem(
                     file_contents,
-                    &_docstring.get_range(),
-                    vec!["Raise:", "Raises:"],
-                );
-                let (line, line_location, _) = exc_lines.first().unwrap().to_owned();
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
+                    &_docstring,
+                    function.def.name(),
+                    "raises",
+                    exc_name.as_str(),
+                    exc_in_docstr_code(),
                     exc_in_docstr_msg(exc_name.as_str()),
                 ));
             }
@@ -459,11 +903,11 @@ fn check_functions_for_extra_exc_in_raises_section(
     problem_functions
 }
 fn check_functions_for_missing_exc_in_raises_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -502,11 +946,13 @@ fn check_functions_for_missing_exc_in_raises_section(
                     function.def.range(),
                     vec![exc_name.as_str()],
                 );
-                let (line, line_location, _) = args_lines.first().unwrap().to_owned();
-                problem_functions.push(format_problem(
-                    line + 2,
-                    line_location,
+                let (line, line_location, found) = args_lines.first().unwrap().to_owned();
+                problem_functions.push(Diagnostic::new(
+                    exc_not_in_docstr_code(),
                     exc_not_in_docstr_msg(exc_name.as_str()),
+                    function.def.name(),
+                    (line + 2, line_location),
+                    (line + 2, line_location + found.chars().count()),
                 ));
             }
         }
@@ -514,11 +960,8 @@ fn check_functions_for_missing_exc_in_raises_section(
 
     problem_functions
 }
-fn get_exc_id(exc: StmtRaise) -> Option<String> {
-    if exc.exc.is_none() {
-        return None;
-    }
-    let _exc = exc.exc.unwrap();
+pub(crate) fn get_exc_id(exc: StmtRaise) -> Option<String> {
+    let _exc = exc.exc?;
 
     if _exc.is_attribute_expr() {
         let _exc = _exc.as_attribute_expr();
@@ -537,10 +980,8 @@ fn get_exc_id(exc: StmtRaise) -> Option<String> {
         } else if some_func.is_name_expr() {
             let some_exp = some_func.as_name_expr();
             Some(some_exp.unwrap().id.to_string())
-        } else if some_func.is_lambda_expr() {
-            None
-            // Some("Lambda".to_string())
         } else {
+            // lambdas and other callables have no usable name
             None
         }
     } else {
@@ -548,11 +989,11 @@ fn get_exc_id(exc: StmtRaise) -> Option<String> {
     }
 }
 fn check_functions_for_missing_arg_in_args_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -574,12 +1015,9 @@ fn check_functions_for_missing_arg_in_args_section(
         let docstring_args_sections = function.docstring.clone().unwrap().get_args_sections();
         let docstring_args = function.docstring.clone().unwrap().get_args();
 
-        println!("{}", function.docstring.clone().unwrap().__repr__());
         if docstring_args_sections.is_empty() {
             continue;
         }
-        println!("docstring_args: {:?}", docstring_args);
-        println!("clean_args: {:?}", clean_args);
         let mut _range = function.def.range();
         // if DC022 is here we don't need to check for DC023
         if function
@@ -593,43 +1031,63 @@ fn check_functions_for_missing_arg_in_args_section(
             continue;
         }
 
-        if clean_args.vararg.is_some() {
-            let arg_name = clean_args.vararg.unwrap().arg.to_string();
-            if let Some(_result) =
-                is_arg_in_docstring(arg_name, &docstring_args, _range, file_contents)
-            {
+        if let Some(vararg) = clean_args.vararg {
+            let arg_name = vararg.arg.to_string();
+            if let Some(_result) = is_arg_in_docstring(
+                arg_name,
+                &docstring_args,
+                _range,
+                file_contents,
+                function.def.name(),
+            ) {
                 problem_functions.push(_result);
             }
         }
-        if clean_args.kwarg.is_some() {
-            let arg_name = clean_args.kwarg.unwrap().arg.to_string();
-            if let Some(_result) =
-                is_arg_in_docstring(arg_name, &docstring_args, _range, file_contents)
-            {
+        if let Some(kwarg) = clean_args.kwarg {
+            let arg_name = kwarg.arg.to_string();
+            if let Some(_result) = is_arg_in_docstring(
+                arg_name,
+                &docstring_args,
+                _range,
+                file_contents,
+                function.def.name(),
+            ) {
                 problem_functions.push(_result);
             }
         }
         for arg in clean_args.args {
             let arg_name = arg.def.arg.to_string();
-            if let Some(_result) =
-                is_arg_in_docstring(arg_name, &docstring_args, _range, file_contents)
-            {
+            if let Some(_result) = is_arg_in_docstring(
+                arg_name,
+                &docstring_args,
+                _range,
+                file_contents,
+                function.def.name(),
+            ) {
                 problem_functions.push(_result);
             }
         }
         for arg in clean_args.kwonlyargs {
             let arg_name = arg.def.arg.to_string();
-            if let Some(_result) =
-                is_arg_in_docstring(arg_name, &docstring_args, _range, file_contents)
-            {
+            if let Some(_result) = is_arg_in_docstring(
+                arg_name,
+                &docstring_args,
+                _range,
+                file_contents,
+                function.def.name(),
+            ) {
                 problem_functions.push(_result);
             }
         }
         for arg in clean_args.posonlyargs {
             let arg_name = arg.def.arg.to_string();
-            if let Some(_result) =
-                is_arg_in_docstring(arg_name, &docstring_args, _range, file_contents)
-            {
+            if let Some(_result) = is_arg_in_docstring(
+                arg_name,
+                &docstring_args,
+                _range,
+                file_contents,
+                function.def.name(),
+            ) {
                 problem_functions.push(_result);
             }
         }
@@ -640,31 +1098,30 @@ fn check_functions_for_missing_arg_in_args_section(
 
 fn is_arg_in_docstring(
     arg_name: String,
-    docstring_args: &Vec<String>,
+    docstring_args: &[String],
     _range: &TextRange,
     file_contents: &str,
-) -> Option<String> {
-    println!(
-        "arg: {}, docstring_args: {:?}, contains",
-        arg_name, docstring_args
-    );
+    symbol: &str,
+) -> Option<Diagnostic> {
     if !docstring_args.contains(&arg_name) {
         let args_lines = find_string_in_text_range(file_contents, _range, vec![arg_name.as_str()]);
-        let (line, line_location, _) = args_lines.first().unwrap().to_owned();
-        return Some(format_problem(
-            line + 2,
-            line_location,
+        let (line, line_location, found) = args_lines.first().unwrap().to_owned();
+        return Some(Diagnostic::new(
+            arg_not_in_docstr_code(),
             arg_not_in_docstr_msg(arg_name.as_str()),
+            symbol,
+            (line + 2, line_location),
+            (line + 2, line_location + found.chars().count()),
         ));
     }
     None
 }
 fn check_functions_for_multiple_args_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -672,9 +1129,9 @@ fn check_functions_for_multiple_args_section(
         }
 
         // ignore if function doesn't have docstrings
-        if function.docstring.is_none() {
+        let Some(docstring) = function.docstring.as_ref() else {
             continue;
-        }
+        };
 
         let args = function.def.args();
         let clean_args = cleanse_args(args, true);
@@ -682,34 +1139,20 @@ fn check_functions_for_multiple_args_section(
         if is_args_empty(&clean_args) {
             continue;
         }
-        if function
-            .docstring
-            .clone()
-            .unwrap()
-            .get_args_sections()
-            .len()
-            > 1
-        {
-            let mut _range = function.def.range();
+        if docstring.get_args_sections().len() > 1 {
             let args_lines = find_string_in_text_range(
                 file_contents,
-                _range,
+                function.def.range(),
                 vec!["Args:", "Arguments:", "Parameters:"],
             );
-            println!("args_lines: {:?}", args_lines);
-            if args_lines.len() < 2 {
-                continue;
-            }
-            let mut founds: Vec<String> = Vec::new();
-            for (_, _, found) in &args_lines {
-                // the latest char is a : which we do not want
-                founds.push(found[..found.len() - 1].to_string());
-            }
-            let (line, line_location, _) = args_lines.first().unwrap().to_owned();
-            problem_functions.push(format_problem(
-                line,
-                line_location,
-                mult_args_sections_in_docstr_msg(founds.join(",").as_str()),
+            problem_functions.push(multiple_sections_problem(
+                file_contents,
+                docstring,
+                function.def.name(),
+                args_lines,
+                docstring.get_args_sections(),
+                mult_args_sections_in_docstr_code(),
+                mult_args_sections_in_docstr_msg,
             ));
         }
     }
@@ -718,11 +1161,11 @@ fn check_functions_for_multiple_args_section(
 }
 
 fn check_functions_for_multiple_yields_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -730,27 +1173,24 @@ fn check_functions_for_multiple_yields_section(
         }
 
         // ignore if function doesn't have docstrings
-        if function.docstring.is_none() {
+        let Some(docstring) = function.docstring.as_ref() else {
             continue;
-        }
+        };
 
-        if function.docstring.clone().unwrap().get_yields().len() > 1 {
-            let mut _range = function.def.range();
-            let yield_lines =
-                find_string_in_text_range(file_contents, _range, vec!["Yield:", "Yields:"]);
-            if yield_lines.len() < 2 {
-                continue;
-            }
-            let mut founds: Vec<String> = Vec::new();
-            for (_, _, found) in &yield_lines {
-                // the latest char is a : which we do not want
-                founds.push(found[..found.len() - 1].to_string());
-            }
-            let (line, line_location, _) = yield_lines.first().unwrap().to_owned();
-            problem_functions.push(format_problem(
-                line,
-                line_location,
-                mult_yields_sections_in_docstr_msg(founds.join(",").as_str()),
+        if docstring.get_yields().len() > 1 {
+            let yields_lines = find_string_in_text_range(
+                file_contents,
+                function.def.range(),
+                vec!["Yield:", "Yields:"],
+            );
+            problem_functions.push(multiple_sections_problem(
+                file_contents,
+                docstring,
+                function.def.name(),
+                yields_lines,
+                docstring.get_yields(),
+                mult_yields_sections_in_docstr_code(),
+                mult_yields_sections_in_docstr_msg,
             ));
         }
     }
@@ -758,11 +1198,11 @@ fn check_functions_for_multiple_yields_section(
     problem_functions
 }
 fn check_functions_for_multiple_raises_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -770,31 +1210,24 @@ fn check_functions_for_multiple_raises_section(
         }
 
         // ignore if function doesn't have docstrings
-        if function.docstring.is_none() {
+        let Some(docstring) = function.docstring.as_ref() else {
             continue;
-        }
-
-        let _docstring = function.docstring.clone().unwrap();
+        };
 
-        if _docstring.get_raises_sections().len() > 1 {
-            let raise_lines = find_string_in_text_range(
+        if docstring.get_raises_sections().len() > 1 {
+            let raises_lines = find_string_in_text_range(
                 file_contents,
-                &_docstring.get_range(),
+                &docstring.get_range(),
                 vec!["Raises:", "Raise:"],
             );
-            if raise_lines.len() < 2 {
-                continue;
-            }
-            let mut founds: Vec<String> = Vec::new();
-            for (_, _, found) in &raise_lines {
-                // the latest char is a : which we do not want
-                founds.push(found[..found.len() - 1].to_string());
-            }
-            let (line, line_location, _) = raise_lines.first().unwrap().to_owned();
-            problem_functions.push(format_problem(
-                line,
-                line_location,
-                mult_raises_sections_in_docstr_msg(founds.join(",").as_str()),
+            problem_functions.push(multiple_sections_problem(
+                file_contents,
+                docstring,
+                function.def.name(),
+                raises_lines,
+                docstring.get_raises_sections(),
+                mult_raises_sections_in_docstr_code(),
+                mult_raises_sections_in_docstr_msg,
             ));
         }
     }
@@ -802,11 +1235,11 @@ fn check_functions_for_multiple_raises_section(
     problem_functions
 }
 fn check_functions_for_multiple_returns_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -814,39 +1247,73 @@ fn check_functions_for_multiple_returns_section(
         }
 
         // ignore if function doesn't have docstrings
-        if function.docstring.is_none() {
+        let Some(docstring) = function.docstring.as_ref() else {
             continue;
-        }
+        };
 
-        if function.docstring.clone().unwrap().get_returns().len() > 1 {
-            let mut _range = function.def.range();
-            let return_lines =
-                find_string_in_text_range(file_contents, _range, vec!["Return:", "Returns:"]);
-            if return_lines.len() < 2 {
-                continue;
-            }
-            let mut founds: Vec<String> = Vec::new();
-            for (_, _, found) in &return_lines {
-                // the latest char is a : which we do not want
-                founds.push(found[..found.len() - 1].to_string());
-            }
-            let (line, line_location, _) = return_lines.first().unwrap().to_owned();
-            problem_functions.push(format_problem(
-                line,
-                line_location,
-                mult_returns_sections_in_docstr_msg(founds.join(",").as_str()),
+        if docstring.get_returns().len() > 1 {
+            let returns_lines = find_string_in_text_range(
+                file_contents,
+                function.def.range(),
+                vec!["Return:", "Returns:"],
+            );
+            problem_functions.push(multiple_sections_problem(
+                file_contents,
+                docstring,
+                function.def.name(),
+                returns_lines,
+                docstring.get_returns(),
+                mult_returns_sections_in_docstr_code(),
+                mult_returns_sections_in_docstr_msg,
             ));
         }
     }
 
     problem_functions
 }
+
+/// The problem of a docstring with more than one section of a kind, at the
+/// first of the section `headers` found in it. NumPy style section names
+/// have no colon to search for, so with fewer than two headers found it is
+/// the whole docstring, listing the section `names`.
+fn multiple_sections_problem(
+    file_contents: &str,
+    docstring: &Docstring,
+    symbol: &str,
+    headers: Vec<(usize, usize, String)>,
+    names: Vec<String>,
+    code: String,
+    error_msg: fn(&str) -> String,
+) -> Diagnostic {
+    if headers.len() < 2 {
+        return docstring_problem(
+            file_contents,
+            docstring,
+            symbol,
+            code,
+            error_msg(names.join(",").as_str()),
+        );
+    }
+    // the latest char is a : which we do not want
+    let founds: Vec<&str> = headers
+        .iter()
+        .map(|(_, _, found)| &found[..found.len() - 1])
+        .collect();
+    let (line, line_location, found) = &headers[0];
+    Diagnostic::new(
+        code,
+        error_msg(founds.join(",").as_str()),
+        symbol,
+        (*line, *line_location),
+        (*line, line_location + found.chars().count()),
+    )
+}
 fn check_functions_for_extra_args_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip_dont_skip_private(function, is_test_file) {
@@ -867,86 +1334,59 @@ fn check_functions_for_extra_args_section(
             continue;
         }
 
-        if function.docstring.clone().unwrap().has_args_sections() {
-            let mut _range = function.docstring.clone().unwrap().get_range();
-            let args_lines = find_string_in_text_range(file_contents, &_range, vec!["Args:"]);
-            if args_lines.is_empty() {
-                continue;
-            }
-
-            for (line, line_location, _) in args_lines {
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
-                    args_section_in_docstr_msg(),
-                ));
-            }
+        let _docstring = function.docstring.clone().unwrap();
+        if _docstring.has_args_sections() {
+            problem_functions.extend(section_problems(
+                file_contents,
+                &_docstring,
+                function.def.name(),
+                "args",
+                args_section_in_docstr_code(),
+                args_section_in_docstr_msg(),
+            ));
         }
     }
 
     problem_functions
 }
 
-fn cleanse_args(args: &Arguments, del_private_args: bool) -> Arguments {
+/// `args` without `self`/ `cls`, and without the `_private` ones when
+/// `del_private_args` is set: the arguments a docstring should describe.
+pub(crate) fn cleanse_args(args: &Arguments, del_private_args: bool) -> Arguments {
+    let is_hidden = |arg_name: &str| {
+        let arg_name = arg_name.trim();
+        arg_name == "self" || arg_name == "cls" || (del_private_args && arg_name.starts_with('_'))
+    };
     let mut clean_args: Arguments = args.clone();
-    if args.vararg.is_some() {
-        let arg_name = args.vararg.clone().unwrap().arg.trim().to_owned();
-        if arg_name == "self" {
-            clean_args.vararg = None;
-        }
-        if arg_name == "cls" {
-            clean_args.vararg = None;
-        }
-        if del_private_args && arg_name.starts_with("_") {
-            clean_args.vararg = None;
-        }
-    }
-    if args.kwarg.is_some() {
-        let arg_name = args.kwarg.clone().unwrap().arg.trim().to_owned();
-        if del_private_args && arg_name.starts_with("_") {
-            clean_args.kwarg = None;
-        }
+    if clean_args
+        .vararg
+        .as_ref()
+        .is_some_and(|vararg| is_hidden(&vararg.arg))
+    {
+        clean_args.vararg = None;
     }
-    for (index, arg) in args.args.iter().enumerate() {
-        let arg_name = arg.def.arg.trim();
-        if arg_name == "self" {
-            clean_args.args.remove(index);
-        }
-        if arg_name == "cls" {
-            clean_args.args.remove(index);
-        }
-        if del_private_args && arg_name.starts_with("_") {
-            clean_args.args.remove(index);
-        }
-    }
-
-    for (index, arg) in args.kwonlyargs.iter().enumerate() {
-        let arg_name = arg.def.arg.trim();
-        if del_private_args && arg_name.starts_with("_") {
-            clean_args.kwonlyargs.remove(index);
-        }
-    }
-    for (index, arg) in args.posonlyargs.iter().enumerate() {
-        let arg_name = arg.def.arg.trim();
-        if arg_name == "self" {
-            clean_args.posonlyargs.remove(index);
-        }
-        if arg_name == "cls" {
-            clean_args.posonlyargs.remove(index);
-        }
-        if del_private_args && arg_name.starts_with("_") {
-            clean_args.posonlyargs.remove(index);
-        }
+    if clean_args.kwarg.as_ref().is_some_and(|kwarg| {
+        let arg_name = kwarg.arg.trim();
+        del_private_args && arg_name.starts_with('_')
+    }) {
+        clean_args.kwarg = None;
     }
+    clean_args.args.retain(|arg| !is_hidden(&arg.def.arg));
+    clean_args
+        .kwonlyargs
+        .retain(|arg| !(del_private_args && arg.def.arg.trim().starts_with('_')));
+    clean_args
+        .posonlyargs
+        .retain(|arg| !is_hidden(&arg.def.arg));
     clean_args
 }
 
 fn check_functions_for_extra_yields_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip_dont_skip_private(function, is_test_file) {
@@ -964,18 +1404,14 @@ fn check_functions_for_extra_yields_section(
             && is_yield_empty(&file_contents, yield_statements.first().unwrap()))
             || yield_statements.is_empty() && function.docstring.clone().unwrap().has_yields()
         {
-            let mut _range = function.def.range();
-            let yield_lines = find_string_in_text_range(file_contents, _range, vec!["Yields:"]);
-            if yield_lines.is_empty() {
-                continue;
-            }
-            for (line, line_location, _) in yield_lines {
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
-                    yields_section_in_docstr_msg(),
-                ));
-            }
+            problem_functions.extend(section_problems(
+                file_contents,
+                &function.docstring.clone().unwrap(),
+                function.def.name(),
+                "yields",
+                yields_section_in_docstr_code(),
+                yields_section_in_docstr_msg(),
+            ));
         }
     }
 
@@ -983,11 +1419,11 @@ fn check_functions_for_extra_yields_section(
 }
 
 fn check_functions_for_extra_raises_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip_dont_skip_private(function, is_test_file) {
@@ -1000,32 +1436,25 @@ fn check_functions_for_extra_raises_section(
         let _docstring = function.docstring.clone().unwrap();
 
         if function.raises.is_empty() && _docstring.has_raises_sections() {
-            let raise_lines = find_string_in_text_range(
+            problem_functions.extend(section_problems(
                 file_contents,
-                &_docstring.get_range(),
-                vec!["Raise:", "Raises:"],
-            );
-            if raise_lines.is_empty() {
-                continue;
-            }
-            for (line, line_location, _) in raise_lines {
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
-                    raises_section_in_docstr_msg(),
-                ));
-            }
+                &_docstring,
+                function.def.name(),
+                "raises",
+                raises_section_in_docstr_code(),
+                raises_section_in_docstr_msg(),
+            ));
         }
     }
 
     problem_functions
 }
 fn check_functions_for_extra_returns_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip_dont_skip_private(function, is_test_file) {
@@ -1043,18 +1472,14 @@ fn check_functions_for_extra_returns_section(
             || return_statements.is_empty())
             && _docstring.has_returns()
         {
-            let return_lines =
-                find_string_in_text_range(file_contents, function.def.range(), vec!["Returns:"]);
-            if return_lines.is_empty() {
-                continue;
-            }
-            for (line, line_location, _) in return_lines {
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
-                    returns_section_in_docstr_msg(),
-                ));
-            }
+            problem_functions.extend(section_problems(
+                file_contents,
+                &_docstring,
+                function.def.name(),
+                "returns",
+                returns_section_in_docstr_code(),
+                returns_section_in_docstr_msg(),
+            ));
         }
     }
 
@@ -1062,11 +1487,11 @@ fn check_functions_for_extra_returns_section(
 }
 
 fn check_functions_for_missing_raises_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -1085,10 +1510,12 @@ fn check_functions_for_missing_raises_section(
             for ret in raise_statements {
                 let (line, line_location) =
                     find_line_and_column(file_contents, ret.range.start().to_usize()).unwrap();
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
+                problem_functions.push(Diagnostic::new(
+                    raises_section_not_in_docstr_code(),
                     raises_section_not_in_docstr_msg(),
+                    function.def.name(),
+                    (line, line_location),
+                    find_range_end(file_contents, &ret.range),
                 ));
             }
         }
@@ -1098,11 +1525,11 @@ fn check_functions_for_missing_raises_section(
 }
 
 fn check_functions_for_missing_yields_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -1126,10 +1553,12 @@ fn check_functions_for_missing_yields_section(
                 }
                 let (line, line_location) =
                     find_line_and_column(file_contents, _range.start().to_usize()).unwrap();
-                problem_functions.push(format_problem(
-                    line,
-                    line_location,
+                problem_functions.push(Diagnostic::new(
+                    yields_section_not_in_docstr_code(),
                     yields_section_not_in_docstr_msg(),
+                    function.def.name(),
+                    (line, line_location),
+                    find_range_end(file_contents, _range),
                 ));
             }
         }
@@ -1158,11 +1587,11 @@ fn is_args_empty(args: &Arguments) -> bool {
 }
 
 fn check_functions_for_missing_args_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -1186,12 +1615,14 @@ fn check_functions_for_missing_args_section(
 
         let _range = function.def.range();
         let doc_loc = find_string_in_text_range(file_contents, _range, vec!["\"\"\""]);
-        let (line, line_location, _) = doc_loc.first().unwrap().to_owned();
+        let (line, line_location, found) = doc_loc.first().unwrap().to_owned();
 
-        problem_functions.push(format_problem(
-            line + 2,
-            line_location,
+        problem_functions.push(Diagnostic::new(
+            args_section_not_in_docstr_code(),
             args_section_not_in_docstr_msg(),
+            function.def.name(),
+            (line + 2, line_location),
+            (line + 2, line_location + found.chars().count()),
         ));
     }
 
@@ -1199,11 +1630,11 @@ fn check_functions_for_missing_args_section(
 }
 
 fn check_functions_for_missing_returns_section(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip(function, is_test_file) {
@@ -1225,10 +1656,12 @@ fn check_functions_for_missing_returns_section(
 
                     let (line, line_location) =
                         find_line_and_column(file_contents, _range.start().to_usize()).unwrap();
-                    problem_functions.push(format_problem(
-                        line,
-                        line_location,
+                    problem_functions.push(Diagnostic::new(
+                        returns_section_not_in_docstr_code(),
                         returns_section_not_in_docstr_msg(),
+                        function.def.name(),
+                        (line, line_location),
+                        find_range_end(file_contents, _range),
                     ));
                 }
             }
@@ -1238,262 +1671,585 @@ fn check_functions_for_missing_returns_section(
     problem_functions
 }
 
-fn generate_rules_output(
-    file_contents: &str,
-    things: &DocstringCollector,
-    is_test_file: bool,
-) -> Vec<String> {
-    // DC0010: docstring missing on a function/ method/ class
-    let mut problem_functions: Vec<String> = Vec::new();
+type FunctionCheck = fn(&[FunctionInfo], &str, bool) -> Vec<Diagnostic>;
+type ClassCheck = fn(&Vec<ClassInfo>, &str) -> Vec<Diagnostic>;
 
+/// Function/ method checks in reporting order, keyed by the code they report.
+const FUNCTION_CHECKS: [(fn() -> String, FunctionCheck); 20] = [
     // DC0010: docstring missing on a function/ method/ class
-    problem_functions.extend(check_functions_for_missing_docstring(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
-
+    (docstr_missing_code, check_functions_for_missing_docstring),
     // DCO030: function/ method that returns a value does not have the returns section in the docstring.
-    problem_functions.extend(check_functions_for_missing_returns_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
-
+    (
+        returns_section_not_in_docstr_code,
+        check_functions_for_missing_returns_section,
+    ),
     // DC031: function/ method that does not return a value should not
     // have the returns section in the docstring
-    problem_functions.extend(check_functions_for_extra_returns_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
-
+    (
+        returns_section_in_docstr_code,
+        check_functions_for_extra_returns_section,
+    ),
     // DC032: a docstring should only contain a single returns
     // section, found %s
-    problem_functions.extend(check_functions_for_multiple_returns_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
-
+    (
+        mult_returns_sections_in_docstr_code,
+        check_functions_for_multiple_returns_section,
+    ),
     // DC040: function/ method that yields a value should have the
     // yields section in the docstring
-    problem_functions.extend(check_functions_for_missing_yields_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
-
+    (
+        yields_section_not_in_docstr_code,
+        check_functions_for_missing_yields_section,
+    ),
     // DC041: function/ method that does not yield a value should not
     // have the yields section in the docstring
-    problem_functions.extend(check_functions_for_extra_yields_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
-
+    (
+        yields_section_in_docstr_code,
+        check_functions_for_extra_yields_section,
+    ),
     // DC042: a docstring should only contain a single yields
     // section, found %s
-    problem_functions.extend(check_functions_for_multiple_yields_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        mult_yields_sections_in_docstr_code,
+        check_functions_for_multiple_yields_section,
+    ),
     // DC020: function/ method with arguments should have the
     // arguments section in the docstring
-    problem_functions.extend(check_functions_for_missing_args_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        args_section_not_in_docstr_code,
+        check_functions_for_missing_args_section,
+    ),
     // DC021: function/ method without arguments should not have the
     // arguments section in the docstring
-    problem_functions.extend(check_functions_for_extra_args_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        args_section_in_docstr_code,
+        check_functions_for_extra_args_section,
+    ),
     // DC022: function/ method without arguments should not have the
     // arguments section in the docstring
-    problem_functions.extend(check_functions_for_multiple_args_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        mult_args_sections_in_docstr_code,
+        check_functions_for_multiple_args_section,
+    ),
     // DC023: argument should be described in the docstring
-    problem_functions.extend(check_functions_for_missing_arg_in_args_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        arg_not_in_docstr_code,
+        check_functions_for_missing_arg_in_args_section,
+    ),
     // DC024: argument should not be described in the docstring
-    problem_functions.extend(check_functions_for_extra_arg_in_args_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        arg_in_docstr_code,
+        check_functions_for_extra_arg_in_args_section,
+    ),
     // DC025: argument documented multiple times
-    problem_functions.extend(check_functions_for_duplicate_arg_in_args_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        duplicate_arg_in_docstr_code,
+        check_functions_for_duplicate_arg_in_args_section,
+    ),
     // DC050: function/ method that raises a value should have the
     // raises section in the docstring
-    problem_functions.extend(check_functions_for_missing_raises_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        raises_section_not_in_docstr_code,
+        check_functions_for_missing_raises_section,
+    ),
     // DC051: function/ method that does not raise a value should not
     // have the raises section in the docstring
-    problem_functions.extend(check_functions_for_extra_raises_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        raises_section_in_docstr_code,
+        check_functions_for_extra_raises_section,
+    ),
     // DC052: a docstring should only contain a single raises
     // section, found %s
-    problem_functions.extend(check_functions_for_multiple_raises_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        mult_raises_sections_in_docstr_code,
+        check_functions_for_multiple_raises_section,
+    ),
     // DC053: exception should be described in the docstring
-    problem_functions.extend(check_functions_for_missing_exc_in_raises_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        exc_not_in_docstr_code,
+        check_functions_for_missing_exc_in_raises_section,
+    ),
     // DC054: exception should not be described in the docstring
-    problem_functions.extend(check_functions_for_extra_exc_in_raises_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        exc_in_docstr_code,
+        check_functions_for_extra_exc_in_raises_section,
+    ),
     // DC055: reraise exception not described in the docstring
-    problem_functions.extend(check_functions_for_re_raise_no_exc_in_raises_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
+    (
+        re_raise_no_exc_in_docstr_code,
+        check_functions_for_re_raise_no_exc_in_raises_section,
+    ),
     // DC056: exception documented multiple times in the docstring
-    problem_functions.extend(check_functions_for_multiple_exc_in_raises_section(
-        &things.function_infos,
-        file_contents,
-        is_test_file,
-    ));
-    for class_info in &things.class_infos {
-        problem_functions.extend(check_functions_for_missing_docstring(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_missing_returns_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_extra_returns_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_multiple_returns_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_missing_yields_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_extra_yields_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_multiple_yields_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_missing_args_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_extra_args_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_multiple_args_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_missing_arg_in_args_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_extra_arg_in_args_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_duplicate_arg_in_args_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_missing_raises_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_extra_raises_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_multiple_raises_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_missing_exc_in_raises_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_extra_exc_in_raises_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_re_raise_no_exc_in_raises_section(
-            &class_info.funcs,
-            file_contents,
-            is_test_file,
-        ));
-        problem_functions.extend(check_functions_for_multiple_exc_in_raises_section(
-            &class_info.funcs,
+    (
+        duplicate_exc_code,
+        check_functions_for_multiple_exc_in_raises_section,
+    ),
+];
+
+/// Class checks in reporting order, keyed by the code they report.
+const CLASS_CHECKS: [(fn() -> String, ClassCheck); 6] = [
+    // DC060: class with public attributes should have the attributes
+    // section in the docstring
+    (
+        attrs_section_not_in_docstr_code,
+        check_classes_for_missing_attrs_section,
+    ),
+    // DC061: class without public attributes should not have the
+    // attributes section in the docstring
+    (
+        attrs_section_in_docstr_code,
+        check_classes_for_extra_attrs_section,
+    ),
+    // DC062: a docstring should only contain a single attributes
+    // section, found %s
+    (
+        mult_attrs_sections_in_docstr_code,
+        check_classes_for_multiple_attrs_section,
+    ),
+    // DC063: attribute should be described in the docstring
+    (
+        attr_not_in_docstr_code,
+        check_classes_for_missing_attr_in_attrs_section,
+    ),
+    // DC064: attribute should not be described in the docstring
+    (
+        attr_in_docstr_code,
+        check_classes_for_extra_attr_in_attrs_section,
+    ),
+    // DC065: attribute documented multiple times
+    (
+        duplicate_attr_in_docstr_code,
+        check_classes_for_duplicate_attr_in_attrs_section,
+    ),
+];
+
+/// Runs the checks whose code passes `is_enabled`, disabled checks are not
+/// run at all.
+fn generate_rules_output(
+    file_contents: &str,
+    things: &DocstringCollector,
+    is_test_file: bool,
+    is_enabled: impl Fn(&str) -> bool,
+) -> Vec<Diagnostic> {
+    let function_checks: Vec<FunctionCheck> = FUNCTION_CHECKS
+        .iter()
+        .filter(|(code, _)| is_enabled(code().as_str()))
+        .map(|(_, check)| *check)
+        .collect();
+    let class_checks: Vec<ClassCheck> = CLASS_CHECKS
+        .iter()
+        .filter(|(code, _)| is_enabled(code().as_str()))
+        .map(|(_, check)| *check)
+        .collect();
+
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
+
+    for check in &function_checks {
+        problem_functions.extend(check_each_function(
+            *check,
+            &things.function_infos,
             file_contents,
             is_test_file,
         ));
     }
+    for class_info in &things.class_infos {
+        for check in &function_checks {
+            problem_functions.extend(check_each_function(
+                *check,
+                &class_info.funcs,
+                file_contents,
+                is_test_file,
+            ));
+        }
+    }
+    for check in &class_checks {
+        problem_functions.extend(check(&things.class_infos, file_contents));
+    }
     problem_functions
 }
 
+/// Runs `check` on one function at a time, recording which definition each
+/// problem belongs to.
+fn check_each_function(
+    check: FunctionCheck,
+    function_infos: &[FunctionInfo],
+    file_contents: &str,
+    is_test_file: bool,
+) -> Vec<Diagnostic> {
+    function_infos
+        .iter()
+        .flat_map(|function| {
+            let range = function.def.range();
+            check(std::slice::from_ref(function), file_contents, is_test_file)
+                .into_iter()
+                .map(move |diagnostic| Diagnostic {
+                    definition: Some((range.start().to_usize(), range.end().to_usize())),
+                    ..diagnostic
+                })
+        })
+        .collect()
+}
+
+/// Collects the attributes of a class: assignments in the class body,
+/// properties and the `self`/ `cls` attributes set in its methods.
+fn get_class_attrs(class_info: &ClassInfo) -> Vec<ClassAttr> {
+    let mut attrs: Vec<ClassAttr> = Vec::new();
+
+    for stmt in &class_info.def.body {
+        let method = match stmt {
+            Stmt::Assign(assign) => {
+                for target in &assign.targets {
+                    attrs.extend(get_target_attrs(target, false));
+                }
+                continue;
+            }
+            Stmt::AnnAssign(assign) => {
+                attrs.extend(get_target_attrs(&assign.target, false));
+                continue;
+            }
+            Stmt::AugAssign(assign) => {
+                attrs.extend(get_target_attrs(&assign.target, false));
+                continue;
+            }
+            Stmt::FunctionDef(func_def) => FunctionDefKind::Sync(func_def.clone()),
+            Stmt::AsyncFunctionDef(func_def) => FunctionDefKind::Async(func_def.clone()),
+            _ => continue,
+        };
+        if is_property(&method) || is_cached_property(method.clone()) {
+            attrs.push(ClassAttr {
+                name: method.name().to_string(),
+                range: *method.range(),
+                in_method: false,
+            });
+        }
+        attrs.extend(get_method_attrs(&method));
+    }
+
+    attrs
+}
+
+fn has_public_class_attrs(attrs: &[ClassAttr]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| !attr.in_method && !attr.is_private())
+}
+
+/// The attributes section is unused when the class has no public attributes
+/// and the section does not describe any of the private ones.
+fn is_attrs_section_unused(docstring: &Docstring, attrs: &[ClassAttr]) -> bool {
+    if attrs.iter().any(|attr| !attr.is_private()) {
+        return false;
+    }
+    let docstring_attrs = docstring.get_attrs();
+    docstring_attrs.is_empty()
+        || docstring_attrs
+            .iter()
+            .any(|name| !attrs.iter().any(|attr| &attr.name == name))
+}
+
+fn docstring_problem(
+    file_contents: &str,
+    docstring: &Docstring,
+    symbol: &str,
+    code: String,
+    error_msg: String,
+) -> Diagnostic {
+    let (line, line_location) =
+        find_line_and_column(file_contents, docstring.get_range().start().to_usize()).unwrap();
+    Diagnostic::new(
+        code,
+        error_msg,
+        symbol,
+        (line, line_location),
+        find_range_end(file_contents, &docstring.get_range()),
+    )
+}
+
+/// The sections of `docstring` as written in `file_contents`, with its text
+/// and the offset the text starts at. `None` when the text is not the value
+/// of the docstring as written, e.g. with escapes.
+fn docstring_spans<'a>(
+    file_contents: &'a str,
+    docstring: &Docstring,
+) -> Option<(usize, &'a str, Vec<SectionSpan>)> {
+    let (offset, text) = docstring_text(file_contents, docstring.get_range())?;
+    Some((
+        offset,
+        text,
+        section_spans(text, Some(docstring.get_style())),
+    ))
+}
+
+/// The (line, column) start and end of the first line of `range` of the
+/// docstring `text` that starts at `offset`, without its indentation.
+fn span_location(
+    file_contents: &str,
+    offset: usize,
+    text: &str,
+    range: &Range<usize>,
+) -> ((usize, usize), (usize, usize)) {
+    let first_line = text[range.clone()].split('\n').next().unwrap_or_default();
+    let start = range.start + first_line.len() - first_line.trim_start().len();
+    let end = range.start + first_line.trim_end().len();
+    (
+        find_line_and_column(file_contents, offset + start).unwrap(),
+        find_line_and_column(file_contents, offset + end.max(start)).unwrap(),
+    )
+}
+
+/// A problem at the header of every `kind` section of `docstring`, or one at
+/// the docstring when the sections cannot be found in `file_contents`.
+fn section_problems(
+    file_contents: &str,
+    docstring: &Docstring,
+    symbol: &str,
+    kind: &str,
+    code: String,
+    error_msg: String,
+) -> Vec<Diagnostic> {
+    let problems: Vec<Diagnostic> = docstring_spans(file_contents, docstring)
+        .map(|(offset, text, spans)| {
+            spans
+                .iter()
+                .filter(|span| span.is(kind))
+                .map(|span| {
+                    let (start, end) = span_location(file_contents, offset, text, &span.range);
+                    Diagnostic::new(code.clone(), error_msg.clone(), symbol, start, end)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if problems.is_empty() {
+        return vec![docstring_problem(
+            file_contents,
+            docstring,
+            symbol,
+            code,
+            error_msg,
+        )];
+    }
+    problems
+}
+
+/// A problem at the first entry for `name` in the `kind` sections of
+/// `docstring`, or at the docstring when it cannot be found in
+/// `file_contents`.
+fn entry_problem(
+    file_contents: &str,
+    docstring: &Docstring,
+    symbol: &str,
+    kind: &str,
+    name: &str,
+    code: String,
+    error_msg: String,
+) -> Diagnostic {
+    let location = docstring_spans(file_contents, docstring).and_then(|(offset, text, spans)| {
+        spans
+            .iter()
+            .filter(|span| span.is(kind))
+            .flat_map(|span| span.entries.iter())
+            .find(|entry| entry.names.iter().any(|entry_name| entry_name == name))
+            .map(|entry| span_location(file_contents, offset, text, &entry.range))
+    });
+    match location {
+        Some((start, end)) => Diagnostic::new(code, error_msg, symbol, start, end),
+        None => docstring_problem(file_contents, docstring, symbol, code, error_msg),
+    }
+}
+
+fn check_classes_for_missing_attrs_section(
+    class_infos: &Vec<ClassInfo>,
+    file_contents: &str,
+) -> Vec<Diagnostic> {
+    let mut problem_classes: Vec<Diagnostic> = Vec::new();
+
+    for class_info in class_infos {
+        // ignore if class doesn't have docstrings
+        let Some(docstring) = &class_info.docstring else {
+            continue;
+        };
+        if docstring.has_attrs_sections() {
+            continue;
+        }
+        if has_public_class_attrs(&get_class_attrs(class_info)) {
+            problem_classes.push(docstring_problem(
+                file_contents,
+                docstring,
+                class_info.def.name.as_str(),
+                attrs_section_not_in_docstr_code(),
+                attrs_section_not_in_docstr_msg(),
+            ));
+        }
+    }
+
+    problem_classes
+}
+
+fn check_classes_for_extra_attrs_section(
+    class_infos: &Vec<ClassInfo>,
+    file_contents: &str,
+) -> Vec<Diagnostic> {
+    let mut problem_classes: Vec<Diagnostic> = Vec::new();
+
+    for class_info in class_infos {
+        let Some(docstring) = &class_info.docstring else {
+            continue;
+        };
+        if !docstring.has_attrs_sections() {
+            continue;
+        }
+        if is_attrs_section_unused(docstring, &get_class_attrs(class_info)) {
+            problem_classes.push(docstring_problem(
+                file_contents,
+                docstring,
+                class_info.def.name.as_str(),
+                attrs_section_in_docstr_code(),
+                attrs_section_in_docstr_msg(),
+            ));
+        }
+    }
+
+    problem_classes
+}
+
+fn check_classes_for_multiple_attrs_section(
+    class_infos: &Vec<ClassInfo>,
+    file_contents: &str,
+) -> Vec<Diagnostic> {
+    let mut problem_classes: Vec<Diagnostic> = Vec::new();
+
+    for class_info in class_infos {
+        let Some(docstring) = &class_info.docstring else {
+            continue;
+        };
+        let attrs_sections = docstring.get_attrs_sections();
+        if attrs_sections.len() > 1 {
+            problem_classes.push(docstring_problem(
+                file_contents,
+                docstring,
+                class_info.def.name.as_str(),
+                mult_attrs_sections_in_docstr_code(),
+                mult_attrs_sections_in_docstr_msg(attrs_sections.join(",").as_str()),
+            ));
+        }
+    }
+
+    problem_classes
+}
+
+fn check_classes_for_missing_attr_in_attrs_section(
+    class_infos: &Vec<ClassInfo>,
+    file_contents: &str,
+) -> Vec<Diagnostic> {
+    let mut problem_classes: Vec<Diagnostic> = Vec::new();
+
+    for class_info in class_infos {
+        let Some(docstring) = &class_info.docstring else {
+            continue;
+        };
+        // DC060 covers docstrings without an attributes section
+        if !docstring.has_attrs_sections() {
+            continue;
+        }
+        let docstring_attrs = docstring.get_attrs();
+        let mut reported: HashSet<String> = HashSet::new();
+        for attr in get_class_attrs(class_info) {
+            // instance attributes set in methods are optional in the docstring
+            if attr.in_method || attr.is_private() {
+                continue;
+            }
+            if docstring_attrs.contains(&attr.name) || !reported.insert(attr.name.clone()) {
+                continue;
+            }
+            let (line, line_location) =
+                find_line_and_column(file_contents, attr.range.start().to_usize()).unwrap();
+            problem_classes.push(Diagnostic::new(
+                attr_not_in_docstr_code(),
+                attr_not_in_docstr_msg(attr.name.as_str()),
+                class_info.def.name.as_str(),
+                (line, line_location),
+                find_range_end(file_contents, &attr.range),
+            ));
+        }
+    }
+
+    problem_classes
+}
+
+fn check_classes_for_extra_attr_in_attrs_section(
+    class_infos: &Vec<ClassInfo>,
+    file_contents: &str,
+) -> Vec<Diagnostic> {
+    let mut problem_classes: Vec<Diagnostic> = Vec::new();
+
+    for class_info in class_infos {
+        let Some(docstring) = &class_info.docstring else {
+            continue;
+        };
+        if !docstring.has_attrs_sections() {
+            continue;
+        }
+        let attrs = get_class_attrs(class_info);
+        // if DC061 is here we don't need to check for DC064
+        if is_attrs_section_unused(docstring, &attrs) {
+            continue;
+        }
+        let mut reported: HashSet<String> = HashSet::new();
+        for attr_name in docstring.get_attrs() {
+            if attrs.iter().any(|attr| attr.name == attr_name)
+                || !reported.insert(attr_name.clone())
+            {
+                continue;
+            }
+            problem_classes.push(docstring_problem(
+                file_contents,
+                docstring,
+                class_info.def.name.as_str(),
+                attr_in_docstr_code(),
+                attr_in_docstr_msg(attr_name.as_str()),
+            ));
+        }
+    }
+
+    problem_classes
+}
+
+fn check_classes_for_duplicate_attr_in_attrs_section(
+    class_infos: &Vec<ClassInfo>,
+    file_contents: &str,
+) -> Vec<Diagnostic> {
+    let mut problem_classes: Vec<Diagnostic> = Vec::new();
+
+    for class_info in class_infos {
+        let Some(docstring) = &class_info.docstring else {
+            continue;
+        };
+        if !docstring.has_attrs_sections() {
+            continue;
+        }
+        if is_attrs_section_unused(docstring, &get_class_attrs(class_info)) {
+            continue;
+        }
+        for attr_name in find_duplicates(&docstring.get_attrs()) {
+            problem_classes.push(docstring_problem(
+                file_contents,
+                docstring,
+                class_info.def.name.as_str(),
+                duplicate_attr_in_docstr_code(),
+                duplicate_attr_msg(attr_name.as_str()),
+            ));
+        }
+    }
+
+    problem_classes
+}
+
 fn check_functions_for_missing_docstring(
-    function_infos: &Vec<FunctionInfo>,
+    function_infos: &[FunctionInfo],
     file_contents: &str,
     is_test_file: bool,
-) -> Vec<String> {
-    let mut problem_functions: Vec<String> = Vec::new();
+) -> Vec<Diagnostic> {
+    let mut problem_functions: Vec<Diagnostic> = Vec::new();
 
     for function in function_infos {
         if should_skip_dont_skip_private(function, is_test_file) {
@@ -1505,14 +2261,20 @@ fn check_functions_for_missing_docstring(
                 find_line_and_column(file_contents, function.def.range().start().to_usize())
                     .unwrap();
 
-            problem_functions.push(format_problem(line, line_location, docstr_missing_msg()));
+            problem_functions.push(Diagnostic::new(
+                docstr_missing_code(),
+                docstr_missing_msg(),
+                function.def.name(),
+                (line, line_location),
+                find_range_end(file_contents, function.def.range()),
+            ));
         }
     }
 
     problem_functions
 }
-fn is_property(function: &FunctionInfo) -> bool {
-    for decorator in function.def.decorator_list() {
+fn is_property(function: &FunctionDefKind) -> bool {
+    for decorator in function.decorator_list() {
         if decorator.is_name_expr() {
             let id = &decorator.as_name_expr().unwrap().id;
             if id.eq_ignore_ascii_case("property") {
@@ -1642,7 +2404,7 @@ fn is_cached_property(function: FunctionDefKind) -> bool {
 
     is_fixture
 }
-fn is_yield_empty(file_contents: &&str, yield_kind: &YieldKind) -> bool {
+pub(crate) fn is_yield_empty(file_contents: &&str, yield_kind: &YieldKind) -> bool {
     let _range: &TextRange = yield_kind.range();
 
     let start = usize::try_from(_range.start().to_u32()).unwrap();
@@ -1668,7 +2430,7 @@ fn should_skip_dont_skip_private(function: &FunctionInfo, is_test_file: bool) ->
     if is_overload(function) {
         return true;
     }
-    if is_property(function) {
+    if is_property(&function.def) {
         return true;
     }
     let func_name = function.def.name().to_string();
@@ -1690,7 +2452,7 @@ fn should_skip(function: &FunctionInfo, is_test_file: bool) -> bool {
     if is_overload(function) {
         return true;
     }
-    if is_property(function) {
+    if is_property(&function.def) {
         return true;
     }
     let func_name = function.def.name().to_string();
@@ -0,0 +1,655 @@
+use crate::config::{Config, CONFIG_FILE_NAMES};
+use crate::constants::{
+    arg_in_docstr_code, arg_not_in_docstr_code, docstr_missing_code, duplicate_arg_in_docstr_code,
+    duplicate_exc_code, exc_in_docstr_code, exc_not_in_docstr_code,
+};
+use crate::diagnostic::{Applicability, Diagnostic, Fix, Severity};
+use crate::rule_engine::{catch_check_panic, lint_code_with_config};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeConfiguration, DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument,
+    DidOpenTextDocument, DidSaveTextDocument, LogMessage, Notification as _, PublishDiagnostics,
+    ShowMessage,
+};
+use lsp_types::request::{CodeActionRequest, RegisterCapability, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams, DidChangeWatchedFilesRegistrationOptions,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    FileSystemWatcher, GlobPattern, InitializeParams, LogMessageParams, MessageType,
+    NumberOrString, Position, PublishDiagnosticsParams, Range, Registration, RegistrationParams,
+    SaveOptions, ServerCapabilities, ShowMessageParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions, TextEdit, Url,
+    WorkspaceEdit,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// An open file, linted from the editor's buffer rather than from disk.
+struct Document {
+    text: String,
+    version: Option<i32>,
+}
+
+/// `vipyrdocs server`: publishes diagnostics for the open Python files and
+/// offers their fixes as quick fixes, over the Language Server Protocol.
+struct Server {
+    connection: Connection,
+    documents: HashMap<Url, Document>,
+    /// The loaded configuration files, dropped when one of them changes.
+    configs: HashMap<PathBuf, Config>,
+}
+
+/// Serves LSP on stdin and stdout until the client shuts the server down.
+pub fn run() -> Result<(), String> {
+    let (connection, io_threads) = Connection::stdio();
+    serve(connection)?;
+    io_threads.join().map_err(|err| err.to_string())
+}
+
+/// Serves LSP on `connection`, from the `initialize` request to `exit`.
+pub fn serve(connection: Connection) -> Result<(), String> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                    include_text: Some(false),
+                })),
+                ..TextDocumentSyncOptions::default()
+            },
+        )),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..ServerCapabilities::default()
+    };
+    let params = connection
+        .initialize(serde_json::to_value(capabilities).map_err(|err| err.to_string())?)
+        .map_err(|err| err.to_string())?;
+    let params: InitializeParams =
+        serde_json::from_value(params).map_err(|err| format!("invalid initialize: {}", err))?;
+
+    let mut server = Server {
+        connection,
+        documents: HashMap::new(),
+        configs: HashMap::new(),
+    };
+    server.watch_config_files(&params);
+    server.main_loop()
+}
+
+impl Server {
+    fn main_loop(&mut self) -> Result<(), String> {
+        let receiver = self.connection.receiver.clone();
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    if self
+                        .connection
+                        .handle_shutdown(&request)
+                        .map_err(|err| err.to_string())?
+                    {
+                        return Ok(());
+                    }
+                    self.handle_request(request);
+                }
+                Message::Notification(notification) => self.handle_notification(notification),
+                // the acknowledgement of the file watchers
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Asks the client to report changes to the configuration files, when
+    /// it lets servers register for them.
+    fn watch_config_files(&self, params: &InitializeParams) {
+        let supported = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.did_change_watched_files)
+            .and_then(|watched| watched.dynamic_registration)
+            .unwrap_or(false);
+        if !supported {
+            return;
+        }
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: CONFIG_FILE_NAMES
+                .iter()
+                .map(|name| FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(format!("**/{}", name)),
+                    kind: None,
+                })
+                .collect(),
+        };
+        let params = RegistrationParams {
+            registrations: vec![Registration {
+                id: "vipyrdocs-config".to_string(),
+                method: DidChangeWatchedFiles::METHOD.to_string(),
+                register_options: serde_json::to_value(options).ok(),
+            }],
+        };
+        let request = Request::new(
+            "vipyrdocs/watch-config".to_string().into(),
+            RegisterCapability::METHOD.to_string(),
+            params,
+        );
+        self.send(Message::Request(request));
+    }
+
+    fn handle_request(&mut self, request: Request) {
+        let response = match request.method.as_str() {
+            CodeActionRequest::METHOD => match serde_json::from_value(request.params) {
+                Ok(params) => Response::new_ok(request.id, self.code_actions(params)),
+                Err(err) => {
+                    Response::new_err(request.id, ErrorCode::InvalidParams as i32, err.to_string())
+                }
+            },
+            _ => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request {}", request.method),
+            ),
+        };
+        self.send(Message::Response(response));
+    }
+
+    fn handle_notification(&mut self, notification: Notification) {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                if let Ok(params) =
+                    serde_json::from_value::<DidOpenTextDocumentParams>(notification.params)
+                {
+                    let document = Document {
+                        text: params.text_document.text,
+                        version: Some(params.text_document.version),
+                    };
+                    self.documents
+                        .insert(params.text_document.uri.clone(), document);
+                    self.publish(&params.text_document.uri);
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                if let Ok(params) =
+                    serde_json::from_value::<DidChangeTextDocumentParams>(notification.params)
+                {
+                    let uri = params.text_document.uri;
+                    // full sync, the last change is the whole buffer
+                    if let (Some(document), Some(change)) = (
+                        self.documents.get_mut(&uri),
+                        params.content_changes.into_iter().last(),
+                    ) {
+                        document.text = change.text;
+                        document.version = Some(params.text_document.version);
+                    }
+                    self.publish(&uri);
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                if let Ok(params) =
+                    serde_json::from_value::<DidSaveTextDocumentParams>(notification.params)
+                {
+                    if is_config_file(&params.text_document.uri) {
+                        self.reload_config();
+                    } else {
+                        self.publish(&params.text_document.uri);
+                    }
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Ok(params) =
+                    serde_json::from_value::<DidCloseTextDocumentParams>(notification.params)
+                {
+                    let uri = params.text_document.uri;
+                    self.documents.remove(&uri);
+                    self.send_diagnostics(uri, Vec::new(), None);
+                }
+            }
+            DidChangeWatchedFiles::METHOD => {
+                if let Ok(params) =
+                    serde_json::from_value::<DidChangeWatchedFilesParams>(notification.params)
+                {
+                    if params
+                        .changes
+                        .iter()
+                        .any(|change| is_config_file(&change.uri))
+                    {
+                        self.reload_config();
+                    }
+                }
+            }
+            DidChangeConfiguration::METHOD => self.reload_config(),
+            _ => {}
+        }
+    }
+
+    /// Reads the configuration files again and lints every open file with
+    /// the new settings.
+    fn reload_config(&mut self) {
+        self.configs.clear();
+        let uris: Vec<Url> = self.documents.keys().cloned().collect();
+        for uri in uris {
+            self.publish(&uri);
+        }
+    }
+
+    /// The configuration of the file at `path`, the defaults for a buffer
+    /// that is not saved yet or when the file is broken.
+    fn config_for(&mut self, path: Option<&Path>) -> Config {
+        let Some(config_path) = path.and_then(Config::find) else {
+            return Config::default();
+        };
+        if let Some(config) = self.configs.get(&config_path) {
+            return config.clone();
+        }
+        let config = match Config::load(&config_path) {
+            Ok(config) => config,
+            Err(errors) => {
+                for error in errors {
+                    self.show_error(&error.to_string());
+                }
+                Config::default()
+            }
+        };
+        self.configs.insert(config_path, config.clone());
+        config
+    }
+
+    /// Lints the buffer of `uri`.
+    fn lint(&mut self, uri: &Url) -> Option<(String, Vec<Diagnostic>)> {
+        let text = self.documents.get(uri)?.text.clone();
+        let path = uri.to_file_path().ok();
+        let config = self.config_for(path.as_deref());
+        let file_name = path
+            .as_ref()
+            .and_then(|path| path.to_str())
+            .map(|path| path.to_string());
+        // a panic in a check is the server's bug, not the buffer's, so it
+        // costs this buffer its diagnostics rather than the whole session
+        let output =
+            catch_check_panic(|| lint_code_with_config(&text, file_name.as_deref(), &config));
+        match output {
+            Ok(output) => Some((text, output)),
+            Err(reason) => {
+                self.log_error(&format!("{} could not be checked: {}", uri, reason));
+                None
+            }
+        }
+    }
+
+    fn publish(&mut self, uri: &Url) {
+        let Some((text, output)) = self.lint(uri) else {
+            return;
+        };
+        let diagnostics = output
+            .iter()
+            .map(|diagnostic| to_lsp_diagnostic(&text, diagnostic))
+            .collect();
+        let version = self
+            .documents
+            .get(uri)
+            .and_then(|document| document.version);
+        self.send_diagnostics(uri.clone(), diagnostics, version);
+    }
+
+    fn send_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+        version: Option<i32>,
+    ) {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.send(Message::Notification(notification));
+    }
+
+    /// One quick fix for every fix of the problems in `params.range`, the
+    /// problems one fix resolves together share it.
+    fn code_actions(&mut self, params: CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = params.text_document.uri;
+        let Some((text, output)) = self.lint(&uri) else {
+            return Vec::new();
+        };
+
+        let mut fixes: Vec<(&Fix, Vec<&Diagnostic>)> = Vec::new();
+        for diagnostic in &output {
+            let Some(fix) = &diagnostic.fix else {
+                continue;
+            };
+            if !overlaps(&to_lsp_range(&text, diagnostic), &params.range) {
+                continue;
+            }
+            match fixes.iter_mut().find(|(other, _)| *other == fix) {
+                Some((_, diagnostics)) => diagnostics.push(diagnostic),
+                None => fixes.push((fix, vec![diagnostic])),
+            }
+        }
+
+        fixes
+            .into_iter()
+            .map(|(fix, diagnostics)| {
+                let edits = fix
+                    .edits
+                    .iter()
+                    .map(|edit| TextEdit {
+                        range: Range {
+                            start: offset_position(&text, edit.start),
+                            end: offset_position(&text, edit.end),
+                        },
+                        new_text: edit.content.clone(),
+                    })
+                    .collect();
+                let safe = fix.applicability == Applicability::Safe;
+                let mut title = fix_title(&diagnostics[0].code).to_string();
+                if !safe {
+                    title.push_str(" (unsafe)");
+                }
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(
+                        diagnostics
+                            .iter()
+                            .map(|diagnostic| to_lsp_diagnostic(&text, diagnostic))
+                            .collect(),
+                    ),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), edits)])),
+                        ..WorkspaceEdit::default()
+                    }),
+                    is_preferred: Some(safe),
+                    ..CodeAction::default()
+                })
+            })
+            .collect()
+    }
+
+    fn show_error(&self, message: &str) {
+        let params = ShowMessageParams {
+            typ: MessageType::ERROR,
+            message: format!("vipyrdocs: {}", message),
+        };
+        let notification = Notification::new(ShowMessage::METHOD.to_string(), params);
+        self.send(Message::Notification(notification));
+    }
+
+    fn log_error(&self, message: &str) {
+        let params = LogMessageParams {
+            typ: MessageType::ERROR,
+            message: format!("vipyrdocs: {}", message),
+        };
+        let notification = Notification::new(LogMessage::METHOD.to_string(), params);
+        self.send(Message::Notification(notification));
+    }
+
+    fn send(&self, message: Message) {
+        // the client is gone, the main loop ends with the connection
+        let _ = self.connection.sender.send(message);
+    }
+}
+
+fn is_config_file(uri: &Url) -> bool {
+    uri.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name))
+}
+
+fn fix_title(code: &str) -> &'static str {
+    if code == docstr_missing_code() {
+        "Insert a docstring"
+    } else if [
+        arg_not_in_docstr_code(),
+        arg_in_docstr_code(),
+        duplicate_arg_in_docstr_code(),
+    ]
+    .contains(&code.to_string())
+    {
+        "Sync the Args section with the signature"
+    } else if code == exc_not_in_docstr_code() || code == duplicate_exc_code() {
+        "Sync the Raises section with the raised exceptions"
+    } else if code == exc_in_docstr_code() {
+        "Remove the exceptions that are never raised"
+    } else {
+        "Fix the docstring"
+    }
+}
+
+fn to_lsp_diagnostic(text: &str, diagnostic: &Diagnostic) -> lsp_types::Diagnostic {
+    lsp_types::Diagnostic {
+        range: to_lsp_range(text, diagnostic),
+        severity: Some(match diagnostic.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+        }),
+        code: Some(NumberOrString::String(diagnostic.code.clone())),
+        source: Some("vipyrdocs".to_string()),
+        message: diagnostic.message.clone(),
+        ..lsp_types::Diagnostic::default()
+    }
+}
+
+fn to_lsp_range(text: &str, diagnostic: &Diagnostic) -> Range {
+    Range {
+        start: line_position(text, diagnostic.line, diagnostic.column),
+        end: line_position(text, diagnostic.end_line, diagnostic.end_column),
+    }
+}
+
+/// The LSP position of the 1-based `line` and the 0-based `column` in
+/// characters, LSP counts UTF-16 code units.
+fn line_position(text: &str, line: usize, column: usize) -> Position {
+    let line_index = line.saturating_sub(1);
+    let character = text.split('\n').nth(line_index).map_or(0, |line| {
+        line.chars()
+            .take(column)
+            .map(|c| c.len_utf16())
+            .sum::<usize>()
+    });
+    Position::new(line_index as u32, character as u32)
+}
+
+/// The LSP position of the byte `offset` in `text`.
+fn offset_position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let line = before.matches('\n').count();
+    let character: usize = before[line_start..].chars().map(|c| c.len_utf16()).sum();
+    Position::new(line as u32, character as u32)
+}
+
+fn overlaps(first: &Range, second: &Range) -> bool {
+    first.start <= second.end && second.start <= first.end
+}
+
+////////// Tests
+
+#[cfg(test)]
+fn next_notification(client: &Connection, method: &str) -> serde_json::Value {
+    loop {
+        let message = client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("the server did not answer");
+        if let Message::Notification(notification) = message {
+            if notification.method == method {
+                return notification.params;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn request(client: &Connection, id: i32, method: &str, params: serde_json::Value) -> Response {
+    client
+        .sender
+        .send(Message::Request(Request::new(
+            id.into(),
+            method.to_string(),
+            params,
+        )))
+        .unwrap();
+    loop {
+        let message = client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("the server did not answer");
+        if let Message::Response(response) = message {
+            assert_eq!(response.id, id.into());
+            return response;
+        }
+    }
+}
+
+#[cfg(test)]
+fn notify(client: &Connection, method: &str, params: serde_json::Value) {
+    client
+        .sender
+        .send(Message::Notification(Notification::new(
+            method.to_string(),
+            params,
+        )))
+        .unwrap();
+}
+
+#[cfg(test)]
+fn published_codes(params: &serde_json::Value) -> Vec<String> {
+    params["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| diagnostic["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+pub fn test_server() {
+    use serde_json::json;
+
+    let dir = std::env::temp_dir().join(format!("vipyrdocs-server-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("vipyrdocs.toml");
+    std::fs::write(&config, "").unwrap();
+    let uri = Url::from_file_path(dir.join("module.py")).unwrap();
+    let config_uri = Url::from_file_path(&config).unwrap();
+
+    let (server, client) = Connection::memory();
+    let thread = std::thread::spawn(move || serve(server));
+    let response = request(
+        &client,
+        1,
+        "initialize",
+        json!({"capabilities": {}, "processId": null, "rootUri": null}),
+    );
+    assert!(
+        response.result.unwrap()["capabilities"]["codeActionProvider"]
+            .as_bool()
+            .unwrap()
+    );
+    notify(&client, "initialized", json!({}));
+
+    // the buffer is linted, not the file on disk
+    let text = "def function_1(arg_1, arg_2):\n    \"\"\"Docstring 1.\n\n    Args:\n        arg_1: Description 1.\n    \"\"\"\n";
+    notify(
+        &client,
+        DidOpenTextDocument::METHOD,
+        json!({"textDocument": {"uri": uri, "languageId": "python", "version": 1, "text": text}}),
+    );
+    let params = next_notification(&client, PublishDiagnostics::METHOD);
+    assert_eq!(published_codes(&params), vec!["D023"]);
+    assert_eq!(params["version"], 1);
+
+    let response = request(
+        &client,
+        2,
+        CodeActionRequest::METHOD,
+        json!({
+            "textDocument": {"uri": uri},
+            "range": {"start": {"line": 0, "character": 0}, "end": {"line": 6, "character": 0}},
+            "context": {"diagnostics": []},
+        }),
+    );
+    let actions = response.result.unwrap();
+    assert_eq!(
+        actions[0]["title"],
+        "Sync the Args section with the signature"
+    );
+    assert_eq!(
+        actions[0]["edit"]["changes"][uri.as_str()],
+        json!([{
+            "range": {"start": {"line": 5, "character": 0}, "end": {"line": 5, "character": 0}},
+            "newText": "        arg_2: TODO.\n",
+        }])
+    );
+
+    // non-ASCII docstrings are checked like any other
+    let text = "def fünction_1(ärg_1):\n    \"\"\"Dócstring é.\n\n    Raises:\n        KéyError: Whén.\n    \"\"\"\n";
+    notify(
+        &client,
+        DidChangeTextDocument::METHOD,
+        json!({
+            "textDocument": {"uri": uri, "version": 2},
+            "contentChanges": [{"text": text}],
+        }),
+    );
+    let params = next_notification(&client, PublishDiagnostics::METHOD);
+    assert_eq!(published_codes(&params), vec!["D020", "D051"]);
+
+    notify(
+        &client,
+        DidChangeTextDocument::METHOD,
+        json!({
+            "textDocument": {"uri": uri, "version": 3},
+            "contentChanges": [{"text": "def function_1():\n    pass\n"}],
+        }),
+    );
+    let params = next_notification(&client, PublishDiagnostics::METHOD);
+    assert_eq!(published_codes(&params), vec!["D010"]);
+    assert_eq!(
+        params["diagnostics"][0]["range"],
+        json!({"start": {"line": 0, "character": 0}, "end": {"line": 1, "character": 8}})
+    );
+
+    // the configuration is read again when it changes
+    std::fs::write(&config, "ignore = [\"DCO010\"]\n").unwrap();
+    notify(
+        &client,
+        DidChangeWatchedFiles::METHOD,
+        json!({"changes": [{"uri": config_uri, "type": 2}]}),
+    );
+    let params = next_notification(&client, PublishDiagnostics::METHOD);
+    assert!(published_codes(&params).is_empty());
+
+    notify(
+        &client,
+        DidCloseTextDocument::METHOD,
+        json!({"textDocument": {"uri": uri}}),
+    );
+    let params = next_notification(&client, PublishDiagnostics::METHOD);
+    assert!(published_codes(&params).is_empty());
+
+    let response = request(&client, 3, "shutdown", json!(null));
+    assert!(response.error.is_none());
+    notify(&client, "exit", json!(null));
+    thread.join().unwrap().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn test_positions_count_utf16() {
+    let text = "x = \"é𝄞\"\ny = 1\n";
+    assert_eq!(line_position(text, 1, 7), Position::new(0, 8));
+    assert_eq!(
+        offset_position(text, text.find('\n').unwrap()),
+        Position::new(0, 9)
+    );
+    assert_eq!(
+        offset_position(text, text.find('y').unwrap()),
+        Position::new(1, 0)
+    );
+}
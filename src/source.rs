@@ -0,0 +1,208 @@
+use encoding_rs::{Encoding, UTF_8};
+use regex::Regex;
+
+lazy_static::lazy_static! {
+    // PEP 263: `# -*- coding: latin-1 -*-` or `# vim: set fileencoding=latin-1 :`
+    static ref CODING_PATTERN: Regex =
+        Regex::new(r"^[ \t\f]*#.*?coding[:=][ \t]*([-\w.]+)").unwrap();
+}
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Decodes the bytes of a Python module: UTF-8 unless a PEP 263 coding
+/// declaration on the first or second line names another encoding.
+pub fn decode_source(bytes: &[u8]) -> Result<String, String> {
+    decode_source_with_encoding(bytes).map(|(text, _)| text)
+}
+
+/// How a module was stored, so a fixed module can be written back the same
+/// way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceEncoding {
+    codec: Codec,
+    bom: bool,
+}
+
+/// The WHATWG labels `encoding_rs` follows take latin-1 and ascii for
+/// windows-1252, so those two are handled the way Python does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Codec {
+    /// Every byte is the character of the same value.
+    Latin1,
+    /// Bytes from 0x80 up are errors.
+    Ascii,
+    Other(&'static Encoding),
+}
+
+/// Like [`decode_source`], also returning the encoding for
+/// [`encode_source`].
+pub fn decode_source_with_encoding(bytes: &[u8]) -> Result<(String, SourceEncoding), String> {
+    let (bytes, bom) = match bytes.strip_prefix(UTF8_BOM) {
+        Some(rest) => (rest, true),
+        None => (bytes, false),
+    };
+
+    let codec = match coding_declaration(bytes) {
+        Some(name) => {
+            let codec = lookup_codec(&name)
+                .ok_or_else(|| format!("unknown encoding in the coding declaration: {}", name))?;
+            if bom && codec != Codec::Other(UTF_8) {
+                return Err(format!("encoding problem: {} with a UTF-8 BOM", name));
+            }
+            codec
+        }
+        None => Codec::Other(UTF_8),
+    };
+
+    let text = match codec {
+        Codec::Latin1 => bytes.iter().map(|&byte| char::from(byte)).collect(),
+        Codec::Ascii => match bytes.iter().position(|byte| !byte.is_ascii()) {
+            Some(position) => {
+                return Err(format!(
+                    "file is not valid ascii: byte 0x{:x} at position {}",
+                    bytes[position], position
+                ))
+            }
+            None => String::from_utf8(bytes.to_vec()).unwrap(),
+        },
+        Codec::Other(encoding) if encoding == UTF_8 => std::str::from_utf8(bytes)
+            .map(str::to_string)
+            .map_err(|err| format!("file is not valid UTF-8: {}", err))?,
+        Codec::Other(encoding) => encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|text| text.into_owned())
+            .ok_or_else(|| format!("file is not valid {}", encoding.name()))?,
+    };
+    Ok((text, SourceEncoding { codec, bom }))
+}
+
+/// Encodes `text` like the module it was decoded from, `None` when the
+/// encoding can not represent it.
+pub fn encode_source(text: &str, source_encoding: SourceEncoding) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    if source_encoding.bom {
+        bytes.extend_from_slice(UTF8_BOM);
+    }
+    match source_encoding.codec {
+        Codec::Latin1 => {
+            for c in text.chars() {
+                bytes.push(u8::try_from(u32::from(c)).ok()?);
+            }
+        }
+        Codec::Ascii => {
+            if !text.is_ascii() {
+                return None;
+            }
+            bytes.extend_from_slice(text.as_bytes());
+        }
+        Codec::Other(encoding) => {
+            let (encoded, _, had_errors) = encoding.encode(text);
+            if had_errors {
+                return None;
+            }
+            bytes.extend_from_slice(&encoded);
+        }
+    }
+    Some(bytes)
+}
+
+/// The encoding named on the first two lines, the second line only counts
+/// when the first one is a comment or blank.
+fn coding_declaration(bytes: &[u8]) -> Option<String> {
+    for line in bytes.split(|byte| *byte == b'\n').take(2) {
+        let line = String::from_utf8_lossy(line);
+        if let Some(captures) = CODING_PATTERN.captures(&line) {
+            return Some(captures[1].to_string());
+        }
+        let trimmed = line.trim_start_matches([' ', '\t', '\x0c', '\r']);
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            return None;
+        }
+    }
+    None
+}
+
+/// Maps a Python codec name onto a codec, Python accepts `_` for `-`
+/// and a few spellings the WHATWG labels do not know.
+fn lookup_codec(name: &str) -> Option<Codec> {
+    let name = name.to_lowercase().replace('_', "-");
+    let label = match name.as_str() {
+        "latin-1" | "latin1" | "iso-8859-1" | "iso8859-1" | "l1" => return Some(Codec::Latin1),
+        "ascii" | "us-ascii" => return Some(Codec::Ascii),
+        "utf8" | "utf-8-sig" => "utf-8",
+        "cp1252" | "windows-1252" => "windows-1252",
+        other => other,
+    };
+    Encoding::for_label(label.as_bytes()).map(Codec::Other)
+}
+
+////////// Tests
+
+#[test]
+pub fn test_decode_source_utf8() {
+    assert_eq!(decode_source(b"x = 1\n").unwrap(), "x = 1\n");
+    assert_eq!(decode_source(b"\xef\xbb\xbfx = 1\n").unwrap(), "x = 1\n");
+    let error = decode_source(b"x = '\xe9'\n").unwrap_err();
+    assert!(error.starts_with("file is not valid UTF-8"), "{}", error);
+}
+
+#[test]
+pub fn test_decode_source_coding_declaration() {
+    assert_eq!(
+        decode_source(b"# -*- coding: latin-1 -*-\nx = '\xe9'\n").unwrap(),
+        "# -*- coding: latin-1 -*-\nx = '\u{e9}'\n"
+    );
+    assert_eq!(
+        decode_source(b"#!/usr/bin/env python\n# vim: set fileencoding=cp1252 :\nx = '\x80'\n")
+            .unwrap(),
+        "#!/usr/bin/env python\n# vim: set fileencoding=cp1252 :\nx = '\u{20ac}'\n"
+    );
+    // only the first two lines count, and only after comments
+    assert!(decode_source(b"x = 1\n# coding: latin-1\ny = '\xe9'\n").is_err());
+    assert!(decode_source(b"\n\n# coding: latin-1\ny = '\xe9'\n").is_err());
+}
+
+#[test]
+pub fn test_decode_source_bad_declaration() {
+    assert_eq!(
+        decode_source(b"# coding: klingon\n").unwrap_err(),
+        "unknown encoding in the coding declaration: klingon"
+    );
+    assert!(decode_source(b"\xef\xbb\xbf# coding: latin-1\n").is_err());
+}
+
+#[test]
+pub fn test_encode_source_round_trip() {
+    for bytes in [
+        b"x = 1\n".to_vec(),
+        b"\xef\xbb\xbfx = '\xc3\xa9'\n".to_vec(),
+        b"# -*- coding: latin-1 -*-\nx = '\xe9'\n".to_vec(),
+    ] {
+        let (text, encoding) = decode_source_with_encoding(&bytes).unwrap();
+        assert_eq!(encode_source(&text, encoding).unwrap(), bytes);
+    }
+    let (_, encoding) = decode_source_with_encoding(b"# coding: ascii\n").unwrap();
+    assert_eq!(encode_source("x = '\u{1f40d}'\n", encoding), None);
+}
+
+#[test]
+pub fn test_decode_source_latin1_c1_controls() {
+    // windows-1252 would read 0x80 as the euro sign
+    let bytes = b"# coding: latin-1\nx = '\x80\x9f'\n".to_vec();
+    let (text, encoding) = decode_source_with_encoding(&bytes).unwrap();
+    assert_eq!(text, "# coding: latin-1\nx = '\u{80}\u{9f}'\n");
+    assert_eq!(encode_source(&text, encoding).unwrap(), bytes);
+    assert_eq!(encode_source("x = '\u{20ac}'\n", encoding), None);
+}
+
+#[test]
+pub fn test_decode_source_ascii() {
+    assert_eq!(
+        decode_source(b"# coding: ascii\nx = 1\n").unwrap(),
+        "# coding: ascii\nx = 1\n"
+    );
+    let error = decode_source(b"# coding: ascii\nx = '\xe9'\n").unwrap_err();
+    assert!(error.starts_with("file is not valid ascii"), "{}", error);
+    let (_, encoding) = decode_source_with_encoding(b"# coding: us-ascii\n").unwrap();
+    assert_eq!(encode_source("x = '\u{e9}'\n", encoding), None);
+}
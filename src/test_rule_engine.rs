@@ -1,4 +1,6 @@
+mod test_noqa;
 #[cfg(test)]
+mod test_rule_01;
 mod test_rule_20;
 mod test_rule_21;
 mod test_rule_22;
@@ -17,9 +19,26 @@ mod test_rule_54;
 mod test_rule_55;
 mod test_rule_56;
 mod test_rule_5x;
+mod test_rule_60;
+mod test_rule_61;
+mod test_rule_62;
+mod test_rule_63;
+mod test_rule_64;
+mod test_rule_65;
+mod test_rule_6x;
+mod test_styles;
 
-use crate::constants::{returns_section_in_docstr_msg, returns_section_not_in_docstr_msg};
-use crate::rule_engine::lint_file;
+use crate::config::Config;
+use crate::constants::{
+    attrs_section_not_in_docstr_code, docstr_missing_code, read_error_code,
+    returns_section_in_docstr_msg, returns_section_not_in_docstr_code,
+    returns_section_not_in_docstr_msg,
+};
+use crate::diagnostic::Severity;
+use crate::rule_engine::{
+    catch_check_panic, check_error_output, definition_ranges, lint_code, lint_code_with_config,
+    lint_file, lint_file_with_config, lint_files,
+};
 use rstest::rstest;
 
 #[test]
@@ -86,7 +105,7 @@ def foo():
         let output = lint_file(code, None);
         println!("{:#?}", output);
         for index in 0..expected.len() {
-            assert_eq!(output[index], expected[index]);
+            assert_eq!(output[index].to_string(), expected[index]);
         }
     }
 }
@@ -196,9 +215,12 @@ fn test_rule_30(#[case] code: &str, #[case] expected: Vec<String>) {
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -213,7 +235,7 @@ def function_1():
     Returns:
     """
 "#,
-    vec![format!("3:4 {}", returns_section_in_docstr_msg())]
+    vec![format!("5:4 {}", returns_section_in_docstr_msg())]
 )]
 #[case::private_function_no_return_returns_in_docstring(
     r#"
@@ -223,7 +245,7 @@ def _function_1():
     Returns:
     """
 "#,
-    vec![format!("3:4 {}", returns_section_in_docstr_msg())]
+    vec![format!("5:4 {}", returns_section_in_docstr_msg())]
 )]
 #[case::method_no_return_returns_in_docstring(
     r#"
@@ -235,7 +257,7 @@ class Class1:
         Returns:
         """
 "#,
-    vec![format!("5:8 {}", returns_section_in_docstr_msg())]
+    vec![format!("7:8 {}", returns_section_in_docstr_msg())]
 )]
 #[case::function_return_no_value_returns_in_docstring(
     r#"
@@ -246,7 +268,7 @@ def function_1():
     """
     return
 "#,
-    vec![format!("3:4 {}", returns_section_in_docstr_msg())]
+    vec![format!("5:4 {}", returns_section_in_docstr_msg())]
 )]
 fn test_rule_31(#[case] code: &str, #[case] expected: Vec<String>) {
     let output = lint_file(code, None);
@@ -254,9 +276,12 @@ fn test_rule_31(#[case] code: &str, #[case] expected: Vec<String>) {
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -271,15 +296,18 @@ def function_1():
     """
     return
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", returns_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", returns_section_in_docstr_msg())];
     let output = lint_file(code, None);
     println!("{:#?}", output);
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -297,9 +325,12 @@ async def function_1():
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -318,9 +349,12 @@ def function_1():
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -338,9 +372,185 @@ def function_1():
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
+
+#[test]
+pub fn test_lint_code_returns_structured_diagnostics() {
+    let code = r#"
+def function_1():
+    """Docstring."""
+    return 1
+"#;
+    let output = lint_code(code, Some("module.py"));
+    assert_eq!(output.len(), 1);
+    let diagnostic = &output[0];
+    assert_eq!(diagnostic.code, returns_section_not_in_docstr_code());
+    assert_eq!(diagnostic.message, returns_section_not_in_docstr_msg());
+    assert_eq!(diagnostic.file.as_deref(), Some("module.py"));
+    assert_eq!(diagnostic.symbol, "function_1");
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!((diagnostic.line, diagnostic.column), (4, 4));
+    assert_eq!((diagnostic.end_line, diagnostic.end_column), (4, 12));
+}
+
+#[test]
+pub fn test_lint_code_reports_class_symbol() {
+    let code = r#"
+class Class1:
+    """Docstring 1."""
+    attr_1 = "value 1"
+"#;
+    let output = lint_code(code, None);
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].code, attrs_section_not_in_docstr_code());
+    assert_eq!(output[0].symbol, "Class1");
+    assert_eq!(output[0].file, None);
+    assert_eq!((output[0].end_line, output[0].end_column), (3, 22));
+}
+
+#[test]
+pub fn test_lint_code_end_column_counts_characters() {
+    let code = r#"
+def function_1(ärg_1):
+    """Docstring 1.
+
+    Args:
+        ärg_1: Description 1.
+        ärg_2: Description 2.
+    """
+"#;
+    let output = lint_code(code, None);
+    assert_eq!(output.len(), 1);
+    assert_eq!((output[0].line, output[0].column), (7, 8));
+    assert_eq!((output[0].end_line, output[0].end_column), (7, 13));
+}
+
+#[test]
+pub fn test_lint_code_with_config_skips_disabled_rules() {
+    let code = r#"
+def function_1(arg_1):
+    return 1
+
+class Class1:
+    """Docstring 1."""
+    attr_1 = "value 1"
+"#;
+    let config = Config {
+        select: Some(vec!["D01".to_string(), "D06".to_string()]),
+        ignore: vec!["D060".to_string()],
+        ..Config::default()
+    };
+    let output = lint_code_with_config(code, None, &config);
+    let codes: Vec<&str> = output.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec![docstr_missing_code().as_str()]);
+
+    let config = Config {
+        per_file_ignores: vec![(
+            glob::Pattern::new("scripts/*").unwrap(),
+            vec!["D0".to_string()],
+        )],
+        ..Config::default()
+    };
+    assert!(lint_code_with_config(code, Some("scripts/run.py"), &config).is_empty());
+}
+
+#[test]
+pub fn test_lint_files_sorted_by_path() {
+    let dir = std::env::temp_dir().join(format!("vipyrdocs-lint-files-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut files = Vec::new();
+    for name in ["c.py", "a.py", "b.py"] {
+        let file = dir.join(name);
+        std::fs::write(&file, "def function_1():\n    pass\n").unwrap();
+        files.push(file.to_str().unwrap().to_string());
+    }
+
+    let results = lint_files(files.clone(), &Config::default());
+    files.sort();
+    let paths: Vec<String> = results.iter().map(|(file, _)| file.clone()).collect();
+    assert_eq!(paths, files);
+    for (file, output) in &results {
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].file.as_deref(), Some(file.as_str()));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn test_lint_files_non_ascii_docstring() {
+    let dir = std::env::temp_dir().join(format!("vipyrdocs-non-ascii-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let code = "def fünction_1(ärg_1):\n    \"\"\"Dócstring é.\n\n    Raises:\n        KéyError: Whén.\n    \"\"\"\n    return ärg_1\n";
+    let mut files = Vec::new();
+    for name in ["a.py", "b.py"] {
+        let file = dir.join(name);
+        std::fs::write(&file, code).unwrap();
+        files.push(file.to_str().unwrap().to_string());
+    }
+
+    let results = lint_files(files, &Config::default());
+    assert_eq!(results.len(), 2);
+    for (_, output) in &results {
+        let codes: Vec<&str> = output
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_str())
+            .collect();
+        assert_eq!(codes, vec!["D030", "D020", "D051"]);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn test_check_panic_reported_as_read_error() {
+    let reason = catch_check_panic(|| -> Vec<()> { panic!("bad rule") }).unwrap_err();
+    assert_eq!(reason, "bad rule");
+    let output = check_error_output(&reason, Some("module.py"), &Config::default());
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].code, read_error_code());
+    assert_eq!(output[0].file.as_deref(), Some("module.py"));
+    assert!(output[0].message.contains("could not be checked: bad rule"));
+}
+
+#[test]
+pub fn test_lint_file_reports_unreadable_file() {
+    let output = lint_file("", Some("does/not/exist.py"));
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].code, read_error_code());
+    assert_eq!(output[0].file.as_deref(), Some("does/not/exist.py"));
+    assert_eq!((output[0].line, output[0].column), (1, 0));
+
+    let config = Config {
+        ignore: vec![read_error_code()],
+        ..Config::default()
+    };
+    assert!(lint_file_with_config("", Some("does/not/exist.py"), &config).is_empty());
+}
+
+#[test]
+pub fn test_definition_ranges() {
+    let code = r#"
+def function_1():
+    pass
+
+class Class1:
+    def method_1(self):
+        return 1
+"#;
+    assert_eq!(
+        definition_ranges(code),
+        vec![
+            ("function_1".to_string(), 2, 3),
+            ("method_1".to_string(), 6, 7),
+            ("Class1".to_string(), 5, 7),
+        ]
+    );
+    assert!(definition_ranges("def function_1(:\n").is_empty());
+}
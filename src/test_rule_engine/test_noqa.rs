@@ -0,0 +1,248 @@
+#[cfg(test)]
+use crate::config::Config;
+use crate::constants::{
+    args_section_not_in_docstr_msg, docstr_missing_msg, returns_section_not_in_docstr_msg,
+    unknown_code_in_noqa_msg, unused_noqa_msg,
+};
+use crate::rule_engine::{lint_code_with_config, lint_file};
+use rstest::rstest;
+
+fn general_test(code: &str, expected: Vec<String>) {
+    let output = lint_file(code, None);
+    println!("{:#?}", output);
+    assert_eq!(output.len(), expected.len());
+    for (index, exp) in expected.iter().enumerate() {
+        assert_eq!(
+            &output[index].to_string(),
+            exp,
+            "Mismatch at output index {}: got `{}`, expected `{}`",
+            index,
+            output[index],
+            exp
+        );
+    }
+}
+
+#[rstest]
+#[case::blanket_on_def_line(
+    r#"
+def function_1(arg_1):  # noqa
+    """Docstring 1."""
+    return 1
+"#
+)]
+#[case::codes_on_def_line(
+    r#"
+def function_1(arg_1):  # noqa: DCO020,DCO030
+    """Docstring 1."""
+    return 1
+"#
+)]
+#[case::codes_with_spaces(
+    r#"
+def function_1(arg_1):  # NOQA: DCO020, DCO030
+    """Docstring 1."""
+    return 1
+"#
+)]
+#[case::prefix(
+    r#"
+def function_1(arg_1):  # noqa: DCO0
+    """Docstring 1."""
+    return 1
+"#
+)]
+#[case::one_line_docstring(
+    r#"
+def function_1(arg_1):
+    """Docstring 1."""  # noqa: DCO020,DCO030
+    return 1
+"#
+)]
+#[case::first_line_of_docstring(
+    r#"
+def function_1(arg_1):
+    """Docstring 1.  # noqa: DCO020,DCO030
+
+    More details.
+    """
+    return 1
+"#
+)]
+#[case::method(
+    r#"
+class Class1:
+    """Docstring."""
+
+    def function_1(self, arg_1):  # noqa: DCO020,DCO030
+        """Docstring 1."""
+        return 1
+"#
+)]
+#[case::closing_line_of_signature(
+    r#"
+def function_1(
+    arg_1,
+) -> int:  # noqa: DCO020,DCO030
+    """Docstring 1."""
+    return 1
+"#
+)]
+#[case::argument_line_of_signature(
+    r#"
+def function_1(
+    arg_1,  # noqa
+):
+    """Docstring 1."""
+    return 1
+"#
+)]
+#[case::file_level(
+    r##"# vipyrdocs: noqa
+
+def function_1(arg_1):
+    """Docstring 1."""
+    return 1
+"##
+)]
+fn test_noqa_suppresses(#[case] code: &str) {
+    general_test(code, vec![]);
+}
+
+#[test]
+fn test_noqa_only_suppresses_listed_codes() {
+    let code: &str = r#"
+def function_1(arg_1):  # noqa: DCO020
+    """Docstring 1."""
+    return 1
+"#;
+    let expected: Vec<String> = vec![format!("4:4 {}", returns_section_not_in_docstr_msg())];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_noqa_only_suppresses_its_symbol() {
+    let code: &str = r#"
+def function_1(arg_1):  # noqa
+    """Docstring 1."""
+
+    def function_2():
+        pass
+
+def function_3(arg_1):
+    """Docstring 3."""
+"#;
+    let expected: Vec<String> = vec![
+        format!("5:4 {}", docstr_missing_msg()),
+        format!("9:4 {}", args_section_not_in_docstr_msg()),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_noqa_in_string_is_ignored() {
+    let code: &str = r##"
+def function_1():
+    return "# noqa"
+"##;
+    let expected: Vec<String> = vec![format!("2:0 {}", docstr_missing_msg())];
+    general_test(code, expected);
+}
+
+fn noqa_check_test(code: &str, expected: Vec<String>) {
+    let config = Config {
+        extend_select: vec!["D00".to_string()],
+        ..Config::default()
+    };
+    let output = lint_code_with_config(code, None, &config);
+    let output: Vec<String> = output.iter().map(|d| d.to_string()).collect();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_noqa_check_not_run_by_default() {
+    let code: &str = r#"
+def function_1():  # noqa: DCO020,DCO999
+    """Docstring 1."""
+"#;
+    general_test(code, vec![]);
+}
+
+#[test]
+fn test_noqa_check_used_directive() {
+    let code: &str = r#"
+def function_1(arg_1):  # noqa: DCO020
+    """Docstring 1."""
+"#;
+    noqa_check_test(code, vec![]);
+}
+
+#[test]
+fn test_noqa_check_unused_blanket_directive() {
+    let code: &str = r#"
+def function_1():  # noqa
+    """Docstring 1."""
+"#;
+    noqa_check_test(code, vec![format!("2:19 {}", unused_noqa_msg(None))]);
+}
+
+#[test]
+fn test_noqa_check_unused_codes() {
+    let code: &str = r#"
+def function_1(arg_1):  # noqa: DCO020,DCO030, DCO050
+    """Docstring 1."""
+"#;
+    noqa_check_test(
+        code,
+        vec![format!("2:24 {}", unused_noqa_msg(Some("D030,D050")))],
+    );
+}
+
+#[test]
+fn test_noqa_check_unused_directive_in_docstring() {
+    let code: &str = r#"
+def function_1():
+    """Docstring 1.  # noqa: DCO020
+
+    More details.
+    """
+"#;
+    noqa_check_test(
+        code,
+        vec![format!("3:21 {}", unused_noqa_msg(Some("D020")))],
+    );
+}
+
+#[test]
+fn test_noqa_check_unknown_code() {
+    let code: &str = r#"
+def function_1(arg_1):  # noqa: DCO020,DCO999,E501
+    """Docstring 1."""
+
+x = 1  # noqa
+"#;
+    noqa_check_test(
+        code,
+        vec![format!("2:24 {}", unknown_code_in_noqa_msg("D999"))],
+    );
+}
+
+#[test]
+fn test_noqa_check_other_tools_codes() {
+    let code: &str = r#"
+import os  # noqa: F401, E501
+import sys  # noqa
+
+
+def function_1():  # noqa: F401, DCO010
+    pass
+
+
+def function_2():  # noqa: F401, DCO020
+    """Docstring 1."""
+"#;
+    noqa_check_test(
+        code,
+        vec![format!("10:19 {}", unused_noqa_msg(Some("D020")))],
+    );
+}
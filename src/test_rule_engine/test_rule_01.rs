@@ -0,0 +1,61 @@
+#[cfg(test)]
+use crate::constants::{parse_error_code, parse_error_msg};
+use crate::rule_engine::{lint_code, lint_file};
+
+fn general_test(code: &str, expected: Vec<String>) {
+    let output = lint_file(code, None);
+    println!("{:#?}", output);
+    assert_eq!(output.len(), expected.len());
+    for (index, exp) in expected.iter().enumerate() {
+        assert_eq!(
+            &output[index].to_string(),
+            exp,
+            "Mismatch at output index {}: got `{}`, expected `{}`",
+            index,
+            output[index],
+            exp
+        );
+    }
+}
+
+#[test]
+fn test_rule_01_python_2_print() {
+    let code: &str = r#"
+def function_1():
+    """Docstring 1."""
+    print "hello"
+"#;
+    let expected: Vec<String> = vec![format!(
+        "4:10 {}",
+        parse_error_msg("invalid syntax. Got unexpected token \"hello\"")
+    )];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_01_merge_conflict_marker() {
+    let code: &str = r#"
+def function_1():
+<<<<<<< HEAD
+    pass
+"#;
+    let expected: Vec<String> = vec![format!(
+        "3:0 {}",
+        parse_error_msg("expected an indented block")
+    )];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_01_only_reports_the_parse_error() {
+    let code: &str = r#"
+def function_1(arg_1):
+    return 1
+
+def function_2(:
+"#;
+    let output = lint_code(code, Some("module.py"));
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].code, parse_error_code());
+    assert_eq!(output[0].file.as_deref(), Some("module.py"));
+}
@@ -8,9 +8,12 @@ fn general_test(code: &str, expected: Vec<String>) {
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -24,7 +27,7 @@ def function_1():
     Args:
     """
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", args_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", args_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -37,7 +40,7 @@ def _function_1():
     Args:
     """
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", args_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", args_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -50,7 +53,7 @@ def function_1(_arg_1):
     Args:
     """
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", args_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", args_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -65,6 +68,6 @@ class Class1:
         Args:
         """
 "#;
-    let expected: Vec<String> = vec![format!("5:8 {}", args_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("7:8 {}", args_section_in_docstr_msg())];
     general_test(code, expected);
 }
@@ -175,9 +175,12 @@ fn general_test(code: &str, expected: Vec<String>) {
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
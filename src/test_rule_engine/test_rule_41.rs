@@ -11,7 +11,7 @@ def function_1():
     Yields:
     """
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", yields_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", yields_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -24,7 +24,7 @@ def _function_1():
     Yields:
     """
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", yields_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", yields_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -39,7 +39,7 @@ class Class1:
         Yields:
         """
 "#;
-    let expected: Vec<String> = vec![format!("5:8 {}", yields_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("7:8 {}", yields_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -53,7 +53,7 @@ def function_1():
     """
     yield
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", yields_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", yields_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -63,10 +63,12 @@ fn general_test(code: &str, expected: Vec<String>) {
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
-
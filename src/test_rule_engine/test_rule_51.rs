@@ -8,9 +8,12 @@ fn general_test(code: &str, expected: Vec<String>) {
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -24,7 +27,7 @@ def function_1():
     Raises:
     """
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", raises_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", raises_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -37,7 +40,7 @@ def _function_1():
     Raises:
     """
 "#;
-    let expected: Vec<String> = vec![format!("3:4 {}", raises_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("5:4 {}", raises_section_in_docstr_msg())];
     general_test(code, expected);
 }
 
@@ -52,6 +55,6 @@ class Class1:
         Raises:
         """
 "#;
-    let expected: Vec<String> = vec![format!("5:8 {}", raises_section_in_docstr_msg())];
+    let expected: Vec<String> = vec![format!("7:8 {}", raises_section_in_docstr_msg())];
     general_test(code, expected);
 }
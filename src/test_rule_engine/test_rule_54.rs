@@ -8,9 +8,12 @@ fn general_test(code: &str, expected: Vec<String>) {
     assert_eq!(output.len(), expected.len());
     for (index, exp) in expected.iter().enumerate() {
         assert_eq!(
-            &output[index], exp,
+            &output[index].to_string(),
+            exp,
             "Mismatch at output index {}: got `{}`, expected `{}`",
-            index, output[index], exp
+            index,
+            output[index],
+            exp
         );
     }
 }
@@ -28,7 +31,7 @@ def function_1():
 "#;
     let expected = vec![
         format!("8:10 {}", exc_not_in_docstr_msg("Exc1")),
-        format!("3:4 {}", exc_in_docstr_msg("Exc2")),
+        format!("6:8 {}", exc_in_docstr_msg("Exc2")),
     ];
     general_test(code, expected);
 }
@@ -47,8 +50,8 @@ def function_1():
 "#;
     let expected = vec![
         format!("9:10 {}", exc_not_in_docstr_msg("Exc1")),
-        format!("3:4 {}", exc_in_docstr_msg("Exc2")),
-        format!("3:4 {}", exc_in_docstr_msg("Exc3")),
+        format!("6:8 {}", exc_in_docstr_msg("Exc2")),
+        format!("7:8 {}", exc_in_docstr_msg("Exc3")),
     ];
     general_test(code, expected);
 }
@@ -69,8 +72,8 @@ def function_1():
     let expected = vec![
         format!("9:10 {}", exc_not_in_docstr_msg("Exc1")),
         format!("10:10 {}", exc_not_in_docstr_msg("Exc2")),
-        format!("3:4 {}", exc_in_docstr_msg("Exc3")),
-        format!("3:4 {}", exc_in_docstr_msg("Exc4")),
+        format!("6:8 {}", exc_in_docstr_msg("Exc3")),
+        format!("7:8 {}", exc_in_docstr_msg("Exc4")),
     ];
     general_test(code, expected);
 }
@@ -90,7 +93,7 @@ def function_1():
 "#;
     let expected = vec![
         format!("9:10 {}", exc_not_in_docstr_msg("Exc1")),
-        format!("3:4 {}", exc_in_docstr_msg("Exc3")),
+        format!("6:8 {}", exc_in_docstr_msg("Exc3")),
     ];
     general_test(code, expected);
 }
@@ -110,7 +113,27 @@ def function_1():
 "#;
     let expected = vec![
         format!("10:10 {}", exc_not_in_docstr_msg("Exc2")),
-        format!("3:4 {}", exc_in_docstr_msg("Exc3")),
+        format!("7:8 {}", exc_in_docstr_msg("Exc3")),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_54_function_non_ascii_docstring() {
+    let code = r#"
+def fünction_1():
+    """Dócstring é.
+
+    Raises:
+        Exc1: Whén.
+        Exc3: Whén.
+    """
+    raise Exc1
+    message = "é"; raise Exc2(message)
+"#;
+    let expected = vec![
+        format!("10:25 {}", exc_not_in_docstr_msg("Exc2")),
+        format!("7:8 {}", exc_in_docstr_msg("Exc3")),
     ];
     general_test(code, expected);
 }
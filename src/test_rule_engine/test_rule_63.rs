@@ -0,0 +1,179 @@
+#[cfg(test)]
+use crate::constants::{attr_in_docstr_msg, attr_not_in_docstr_msg};
+use crate::rule_engine::lint_file;
+
+fn general_test(code: &str, expected: Vec<String>) {
+    let output = lint_file(code, None);
+    println!("{:#?}", output);
+    assert_eq!(output.len(), expected.len());
+    for (index, exp) in expected.iter().enumerate() {
+        assert_eq!(
+            &output[index].to_string(),
+            exp,
+            "Mismatch at output index {}: got `{}`, expected `{}`",
+            index,
+            output[index],
+            exp
+        );
+    }
+}
+
+#[test]
+fn test_rule_63_class_has_single_attr_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("7:4 {}", attr_not_in_docstr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_multiple_assign_attr_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    attr_1 = attr_2 = "value 1"
+"#;
+    let expected: Vec<String> = vec![
+        format!("7:4 {}", attr_not_in_docstr_msg("attr_1")),
+        format!("7:13 {}", attr_not_in_docstr_msg("attr_2")),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_single_double_nested_attr_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    attr_1.nested_attr_1.nested_attr_2 = "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("7:4 {}", attr_not_in_docstr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_single_augmented_attr_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    attr_1 += "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("7:4 {}", attr_not_in_docstr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_single_property_with_assignment_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    @property
+    def attr_1(self):
+        """Docstring 2."""
+        self.attr_2 = "value 2"
+        return "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("8:4 {}", attr_not_in_docstr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_single_async_property_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    @property
+    async def attr_1():
+        """Docstring 2."""
+        return "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("8:4 {}", attr_not_in_docstr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_single_functools_cached_property_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    @functools.cached_property
+    def attr_1():
+        """Docstring 2."""
+        return "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("8:4 {}", attr_not_in_docstr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_single_attr_after_init_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    def __init__(self):
+        """Docstring 2."""
+    attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("9:4 {}", attr_not_in_docstr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_multiple_attrs_first_private_docstring_no_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+    """
+    _attr_1 = "value 1"
+    attr_2 = "value 2"
+"#;
+    let expected: Vec<String> = vec![format!("8:4 {}", attr_not_in_docstr_msg("attr_2"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_63_class_has_single_attr_docstring_attr_different() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_2:
+    """
+    attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![
+        format!("8:4 {}", attr_not_in_docstr_msg("attr_1")),
+        format!("3:4 {}", attr_in_docstr_msg("attr_2")),
+    ];
+    general_test(code, expected);
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+use crate::constants::{attr_in_docstr_msg, attr_not_in_docstr_msg};
+use crate::rule_engine::lint_file;
+
+fn general_test(code: &str, expected: Vec<String>) {
+    let output = lint_file(code, None);
+    println!("{:#?}", output);
+    assert_eq!(output.len(), expected.len());
+    for (index, exp) in expected.iter().enumerate() {
+        assert_eq!(
+            &output[index].to_string(),
+            exp,
+            "Mismatch at output index {}: got `{}`, expected `{}`",
+            index,
+            output[index],
+            exp
+        );
+    }
+}
+
+#[test]
+fn test_rule_64_class_single_attr_docstring_multiple_attrs_different() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_2:
+        attr_3:
+    """
+    attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![
+        format!("9:4 {}", attr_not_in_docstr_msg("attr_1")),
+        format!("3:4 {}", attr_in_docstr_msg("attr_2")),
+        format!("3:4 {}", attr_in_docstr_msg("attr_3")),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_64_class_multiple_attr_docstring_multiple_attrs_second_different() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_3:
+    """
+    attr_1 = "value 1"
+    attr_2 = "value 2"
+"#;
+    let expected: Vec<String> = vec![
+        format!("10:4 {}", attr_not_in_docstr_msg("attr_2")),
+        format!("3:4 {}", attr_in_docstr_msg("attr_3")),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_64_class_attr_in_nested_method_docstring_attr() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_2:
+    """
+    attr_1 = "value 1"
+    def method_1(self):
+        """Docstring 2."""
+        def nested_function_1(self):
+            """Docstring 3."""
+            self.attr_2 = "value 2"
+"#;
+    let expected: Vec<String> = vec![format!("3:4 {}", attr_in_docstr_msg("attr_2"))];
+    general_test(code, expected);
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+use crate::constants::duplicate_attr_msg;
+use crate::rule_engine::lint_file;
+
+fn general_test(code: &str, expected: Vec<String>) {
+    let output = lint_file(code, None);
+    println!("{:#?}", output);
+    assert_eq!(output.len(), expected.len());
+    for (index, exp) in expected.iter().enumerate() {
+        assert_eq!(
+            &output[index].to_string(),
+            exp,
+            "Mismatch at output index {}: got `{}`, expected `{}`",
+            index,
+            output[index],
+            exp
+        );
+    }
+}
+
+#[test]
+fn test_rule_65_class_single_attr_docstring_single_attr_duplicate() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_1:
+    """
+    attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("3:4 {}", duplicate_attr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_65_class_single_private_attr_docstring_single_attr_duplicate() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        _attr_1:
+        _attr_1:
+    """
+    _attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("3:4 {}", duplicate_attr_msg("_attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_65_class_single_attr_docstring_single_attr_duplicate_many() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_1:
+        attr_1:
+    """
+    attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("3:4 {}", duplicate_attr_msg("attr_1"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_65_class_multiple_attr_docstring_duplicate_attr_all() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_1:
+        attr_2:
+        attr_2:
+    """
+    attr_1 = "value 1"
+    attr_2 = "value 2"
+"#;
+    let expected: Vec<String> = vec![
+        format!("3:4 {}", duplicate_attr_msg("attr_1")),
+        format!("3:4 {}", duplicate_attr_msg("attr_2")),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_rule_65_class_single_attr_init_docstring_single_attr_duplicate() {
+    let code: &str = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_1:
+    """
+    def __init__(self):
+        """Docstring 2."""
+        self.attr_1 = "value 1"
+"#;
+    let expected: Vec<String> = vec![format!("3:4 {}", duplicate_attr_msg("attr_1"))];
+    general_test(code, expected);
+}
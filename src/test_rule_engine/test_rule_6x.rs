@@ -0,0 +1,222 @@
+#[cfg(test)]
+use crate::rule_engine::lint_file;
+
+fn general_test(code: &str, expected: Vec<String>) {
+    let output = lint_file(code, None);
+    println!("{:#?}", output);
+    assert_eq!(output.len(), expected.len());
+    for (index, exp) in expected.iter().enumerate() {
+        assert_eq!(
+            &output[index].to_string(),
+            exp,
+            "Mismatch at output index {}: got `{}`, expected `{}`",
+            index,
+            output[index],
+            exp
+        );
+    }
+}
+
+#[test]
+fn test_rule_6x_class_single_attr_docstring_single_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+    """
+    attr_1 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_single_cached_property_docstring_single_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+    """
+    @cached_property
+    def attr_1():
+        """Docstring 2."""
+        return "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_single_attr_typed_in_method_docstring_single_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+    """
+    def method_1(self):
+        """Docstring 2."""
+        self.attr_1: str = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_multiple_attr_in_method_docstring_multiple_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_2:
+    """
+    def method_1(self):
+        """Docstring 2."""
+        self.attr_1 = self.attr_2 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_single_attr_deep_nested_in_method_docstring_single_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+    """
+    def method_1(self):
+        """Docstring 2."""
+        self.attr_1.nested_attr_1.nested_attr_2 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_single_attr_in_async_method_docstring_single_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+    """
+    async def method_1(self):
+        """Docstring 2."""
+        self.attr_1 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_single_attr_in_classmethod_docstring_single_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+    """
+    @classmethod
+    def method_1(cls):
+        """Docstring 2."""
+        cls.attr_1 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_has_multiple_attr_in_multiple_method_docstring_single_attr_second() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_2:
+    """
+    def method_1(self):
+        """Docstring 2."""
+        self.attr_1 = "value 1"
+    def method_2(self):
+        """Docstring 3."""
+        self.attr_2 = "value 2"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_single_private_attr_docstring_single_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        _attr_1:
+    """
+    _attr_1 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_single_private_attr_docstring_no_attrs_section() {
+    let code = r#"
+class Class1:
+    """Docstring 1."""
+    _attr_1 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_has_single_attr_in_init_docstring_no_attrs_section() {
+    let code = r#"
+class Class1:
+    """Docstring 1."""
+    def __init__(self):
+        """Docstring 2."""
+        self.attr_1 = "value 1"
+        var_1 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_class_has_single_property_with_assignment_docstring_both_attr() {
+    let code = r#"
+class Class1:
+    """Docstring 1.
+
+    Attrs:
+        attr_1:
+        attr_2:
+    """
+    @property
+    def attr_1(self):
+        """Docstring 2."""
+        self.attr_2 = "value 2"
+        return "value 1"
+"#;
+    general_test(code, Vec::new());
+}
+
+#[test]
+fn test_rule_6x_nested_class_single_attr_docstring_no_attrs() {
+    let code = r#"
+class Class1:
+    """Docstring 1."""
+    class Class2:
+        """Docstring 2.
+
+        Attrs:
+            attr_1:
+        """
+        attr_1 = "value 1"
+"#;
+    general_test(code, Vec::new());
+}
@@ -0,0 +1,277 @@
+#[cfg(test)]
+use crate::config::Config;
+use crate::constants::{
+    arg_in_docstr_msg, arg_not_in_docstr_msg, args_section_in_docstr_msg,
+    args_section_not_in_docstr_msg, exc_in_docstr_msg, mult_returns_sections_in_docstr_msg,
+    raises_section_in_docstr_msg, raises_section_not_in_docstr_msg, returns_section_in_docstr_msg,
+    returns_section_not_in_docstr_msg, yields_section_in_docstr_msg,
+};
+use crate::docstring::DocstringStyle;
+use crate::rule_engine::{lint_code_with_config, lint_file};
+use rstest::rstest;
+
+fn general_test(code: &str, expected: Vec<String>) {
+    let output = lint_file(code, None);
+    println!("{:#?}", output);
+    assert_eq!(output.len(), expected.len());
+    for (index, exp) in expected.iter().enumerate() {
+        assert_eq!(
+            &output[index].to_string(),
+            exp,
+            "Mismatch at output index {}: got `{}`, expected `{}`",
+            index,
+            output[index],
+            exp
+        );
+    }
+}
+
+#[rstest]
+#[case::numpy(
+    r#"
+def function_1(arg_1, *args, **kwargs):
+    """Docstring 1.
+
+    Parameters
+    ----------
+    arg_1 : int
+        Description 1.
+    *args, **kwargs
+        Passed on.
+
+    Returns
+    -------
+    int
+        The result.
+
+    Raises
+    ------
+    ValueError
+        When it fails.
+    """
+    if arg_1:
+        raise ValueError
+    return 1
+"#
+)]
+#[case::sphinx(
+    r#"
+def function_1(arg_1, *args, **kwargs):
+    """Docstring 1.
+
+    :param int arg_1: Description 1.
+    :param args: Passed on.
+    :param kwargs: Passed on.
+    :raises ValueError: When it fails.
+    :returns: The result.
+    :rtype: int
+    """
+    if arg_1:
+        raise ValueError
+    return 1
+"#
+)]
+fn test_styles_complete_docstring(#[case] code: &str) {
+    general_test(code, vec![]);
+}
+
+#[test]
+fn test_numpy_style_problems() {
+    let code: &str = r#"
+def function_1(arg_1, arg_2):
+    """Docstring 1.
+
+    Parameters
+    ----------
+    arg_1 : int
+        Description 1.
+    arg_3 : int
+        Description 3.
+    """
+    raise ValueError
+"#;
+    let expected: Vec<String> = vec![
+        format!("2:22 {}", arg_not_in_docstr_msg("arg_2")),
+        format!("9:4 {}", arg_in_docstr_msg("arg_3")),
+        format!("12:4 {}", raises_section_not_in_docstr_msg()),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_sphinx_style_problems() {
+    let code: &str = r#"
+def function_1(arg_1):
+    """Docstring 1.
+
+    :returns: The result.
+    :returns: The result again.
+    """
+    return arg_1
+"#;
+    let expected: Vec<String> = vec![
+        format!(
+            "3:5 {}",
+            mult_returns_sections_in_docstr_msg("Returns,Returns")
+        ),
+        format!("3:4 {}", args_section_not_in_docstr_msg()),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_configured_convention() {
+    let code: &str = r#"
+def function_1(arg_1):
+    """Docstring 1.
+
+    Parameters
+    ----------
+    arg_1 : int
+        Description 1.
+
+    Returns
+    -------
+    int
+        The result.
+    """
+    return arg_1
+"#;
+    let config = Config {
+        convention: Some(DocstringStyle::Google),
+        ..Config::default()
+    };
+    let output: Vec<String> = lint_code_with_config(code, None, &config)
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect();
+    assert_eq!(
+        output,
+        vec![
+            format!("15:4 {}", returns_section_not_in_docstr_msg()),
+            format!("3:4 {}", args_section_not_in_docstr_msg()),
+        ]
+    );
+
+    let config = Config {
+        convention: Some(DocstringStyle::Numpy),
+        ..Config::default()
+    };
+    assert!(lint_code_with_config(code, None, &config).is_empty());
+}
+
+#[test]
+fn test_numpy_style_multiple_sections() {
+    let code: &str = r#"
+def function_1(arg_1):
+    """Docstring 1.
+
+    Parameters
+    ----------
+    arg_1 : int
+        Description 1.
+
+    Returns
+    -------
+    int
+        The result.
+
+    Returns
+    -------
+    int
+        The result again.
+    """
+    return arg_1
+"#;
+    let expected: Vec<String> = vec![format!(
+        "3:4 {}",
+        mult_returns_sections_in_docstr_msg("Returns,Returns")
+    )];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_numpy_style_extra_sections() {
+    let code: &str = r#"
+def function_1():
+    """Docstring 1.
+
+    Parameters
+    ----------
+    Yields
+    ------
+    int
+        Description 1.
+
+    Raises
+    ------
+    Exc1
+        Description 2.
+
+    Returns
+    -------
+    int
+        Description 3.
+    """
+"#;
+    let expected: Vec<String> = vec![
+        format!("17:4 {}", returns_section_in_docstr_msg()),
+        format!("7:4 {}", yields_section_in_docstr_msg()),
+        format!("5:4 {}", args_section_in_docstr_msg()),
+        format!("12:4 {}", raises_section_in_docstr_msg()),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_numpy_style_extra_exc_in_raises_section() {
+    let code: &str = r#"
+def function_1():
+    """Docstring 1.
+
+    Raises
+    ------
+    Exc1
+        Description 1.
+    Exc2
+        Description 2.
+    """
+    raise Exc1
+"#;
+    let expected: Vec<String> = vec![format!("9:4 {}", exc_in_docstr_msg("Exc2"))];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_sphinx_style_extra_sections() {
+    let code: &str = r#"
+def function_1():
+    """Docstring 1.
+
+    :yields: Description 1.
+    :raises Exc1: Description 2.
+    :returns: Description 3.
+    """
+"#;
+    let expected: Vec<String> = vec![
+        format!("7:4 {}", returns_section_in_docstr_msg()),
+        format!("5:4 {}", yields_section_in_docstr_msg()),
+        format!("6:4 {}", raises_section_in_docstr_msg()),
+    ];
+    general_test(code, expected);
+}
+
+#[test]
+fn test_sphinx_style_extra_exc_in_raises_section() {
+    let code: &str = r#"
+def function_1():
+    """Docstring 1.
+
+    :raises Exc1: Description 1.
+    :raises Exc2: Description 2.
+    """
+    raise Exc1
+"#;
+    let expected: Vec<String> = vec![format!("6:4 {}", exc_in_docstr_msg("Exc2"))];
+    general_test(code, expected);
+}
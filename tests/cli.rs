@@ -0,0 +1,705 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+fn vipyrdocs(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_vipyrdocs"))
+        .args(args)
+        .output()
+        .expect("failed to run vipyrdocs")
+}
+
+/// Creates a fresh directory under the system temp dir for a single test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vipyrdocs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+const CLEAN_CODE: &str = r#"
+def function_1():
+    """Docstring 1."""
+"#;
+
+const PROBLEM_CODE: &str = r#"
+def function_1():
+    pass
+"#;
+
+#[test]
+fn test_exit_status_clean() {
+    let dir = scratch_dir("exit-clean");
+    let file = dir.join("clean.py");
+    fs::write(&file, CLEAN_CODE).unwrap();
+
+    let output = vipyrdocs(&[file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_status_violations() {
+    let dir = scratch_dir("exit-violations");
+    fs::write(dir.join("problem.py"), PROBLEM_CODE).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_exit_status_exit_zero() {
+    let dir = scratch_dir("exit-zero");
+    fs::write(dir.join("problem.py"), PROBLEM_CODE).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--exit-zero"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_status_missing_path() {
+    let output = vipyrdocs(&["does/not/exist.py"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_exit_status_usage_error() {
+    let output = vipyrdocs(&["--format", "xml", "."]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_config_from_pyproject() {
+    let dir = scratch_dir("config-pyproject");
+    fs::write(dir.join("problem.py"), PROBLEM_CODE).unwrap();
+    fs::write(
+        dir.join("pyproject.toml"),
+        "[tool.vipyrdocs]\nselect = [\"DCO02\"]\n",
+    )
+    .unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--select", "DCO01"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = vipyrdocs(&[
+        dir.to_str().unwrap(),
+        "--select",
+        "DCO01",
+        "--ignore",
+        "DCO010",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_config_exclude() {
+    let dir = scratch_dir("config-exclude");
+    fs::create_dir_all(dir.join("legacy")).unwrap();
+    fs::write(dir.join("legacy").join("problem.py"), PROBLEM_CODE).unwrap();
+    fs::write(dir.join("vipyrdocs.toml"), "exclude = [\"legacy\"]\n").unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_config_differs_between_paths() {
+    let dir = scratch_dir("config-between-paths");
+    for project in ["first", "second"] {
+        fs::create_dir_all(dir.join(project).join("package")).unwrap();
+        fs::write(
+            dir.join(project).join("package").join("problem.py"),
+            PROBLEM_CODE,
+        )
+        .unwrap();
+        fs::write(
+            dir.join(project).join("vipyrdocs.toml"),
+            "select = [\"DCO02\"]\n",
+        )
+        .unwrap();
+    }
+    let first = dir.join("first");
+    let second = dir.join("second");
+
+    let output = vipyrdocs(&[first.to_str().unwrap(), second.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("different configuration files"),
+        "{}",
+        stderr
+    );
+
+    // the same configuration file for both
+    let package = first.join("package");
+    let output = vipyrdocs(&[first.to_str().unwrap(), package.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+
+    let config = first.join("vipyrdocs.toml");
+    let output = vipyrdocs(&[
+        first.to_str().unwrap(),
+        second.to_str().unwrap(),
+        "--config",
+        config.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+}
+
+#[test]
+fn test_config_error() {
+    let dir = scratch_dir("config-error");
+    fs::write(dir.join("clean.py"), CLEAN_CODE).unwrap();
+    fs::write(
+        dir.join("vipyrdocs.toml"),
+        "\nselect = [\"DCO010\"]\nignored = []\n",
+    )
+    .unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("vipyrdocs.toml:3: unknown key \"ignored\""),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_extend_select() {
+    let dir = scratch_dir("extend-select");
+    fs::write(dir.join("problem.py"), PROBLEM_CODE).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--select", "DCO02"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = vipyrdocs(&[
+        dir.to_str().unwrap(),
+        "--select",
+        "DCO02",
+        "--extend-select",
+        "DCO010",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--select", "DCO99"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_jobs_output_sorted_by_path() {
+    let dir = scratch_dir("jobs");
+    for name in ["d.py", "b.py", "c.py", "a.py"] {
+        fs::write(dir.join(name), PROBLEM_CODE).unwrap();
+    }
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--jobs", "3", "--format", "jsonl"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let files: Vec<&str> = stdout
+        .lines()
+        .map(|line| {
+            let start = line.find("\"file\":\"").unwrap() + 8;
+            &line[start..start + line[start..].find('"').unwrap()]
+        })
+        .collect();
+    let mut sorted = files.clone();
+    sorted.sort();
+    assert_eq!(files.len(), 4);
+    assert_eq!(files, sorted);
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--jobs", "0"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_syntax_error_is_reported() {
+    let dir = scratch_dir("syntax-error");
+    fs::write(dir.join("a_broken.py"), "def function_1(:\n").unwrap();
+    fs::write(dir.join("b_problem.py"), PROBLEM_CODE).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--format", "jsonl"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", stdout);
+    assert!(lines[0].contains("\"code\":\"D001\""), "{}", stdout);
+    assert!(lines[1].contains("\"code\":\"D010\""), "{}", stdout);
+}
+
+#[test]
+fn test_undecodable_file_is_reported() {
+    let dir = scratch_dir("undecodable");
+    fs::write(dir.join("a_latin1.py"), b"x = '\xe9'\n").unwrap();
+    fs::write(dir.join("b_clean.py"), CLEAN_CODE).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--format", "jsonl", "--exit-zero"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "{}", stdout);
+    assert!(lines[0].contains("\"code\":\"D004\""), "{}", stdout);
+    assert!(lines[0].contains("a_latin1.py"), "{}", stdout);
+}
+
+#[test]
+fn test_coding_declaration_is_honored() {
+    let dir = scratch_dir("coding-declaration");
+    let mut code = b"# -*- coding: latin-1 -*-\n".to_vec();
+    code.extend_from_slice(b"def function_1():\n    \"\"\"Caf\xe9.\"\"\"\n");
+    fs::write(dir.join("latin1.py"), code).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+}
+
+#[test]
+fn test_latin1_docstring_problems_are_reported() {
+    let dir = scratch_dir("coding-latin1-problems");
+    let mut code = b"# -*- coding: latin-1 -*-\n".to_vec();
+    code.extend_from_slice(
+        b"def function_1():\n    \"\"\"Caf\xe9.\n\n    Raises:\n        Exc1: Wh\xe9n.\n        Exc2: Wh\xe9n.\n    \"\"\"\n    message = '\xe9'; raise Exc1(message)\n",
+    );
+    fs::write(dir.join("latin1.py"), code).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--format", "jsonl"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "{}", stdout);
+    assert!(lines[0].contains("\"code\":\"D054\""), "{}", stdout);
+}
+
+#[test]
+fn test_discovery_skips_ignored_files() {
+    let dir = scratch_dir("discovery-ignored");
+    for skipped in [".venv", "build", "generated"] {
+        fs::create_dir_all(dir.join(skipped)).unwrap();
+        fs::write(dir.join(skipped).join("problem.py"), PROBLEM_CODE).unwrap();
+    }
+    fs::write(dir.join(".gitignore"), "generated/\n").unwrap();
+    fs::write(dir.join(".ignore"), "scratch.py\n").unwrap();
+    fs::write(dir.join("scratch.py"), PROBLEM_CODE).unwrap();
+    fs::write(dir.join("stub.pyi"), PROBLEM_CODE).unwrap();
+    fs::write(dir.join("clean.py"), CLEAN_CODE).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--exclude", "generated"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("build"), "{}", stdout);
+    assert!(stdout.contains(".venv"), "{}", stdout);
+}
+
+#[test]
+fn test_discovery_extend_exclude_and_stubs() {
+    let dir = scratch_dir("discovery-stubs");
+    fs::write(dir.join("stub.pyi"), PROBLEM_CODE).unwrap();
+    fs::write(dir.join("problem.py"), PROBLEM_CODE).unwrap();
+    fs::write(dir.join("vipyrdocs.toml"), "extend-include = [\"*.pyi\"]\n").unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--extend-exclude", "problem.py"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("stub.pyi"), "{}", stdout);
+    assert!(!stdout.contains("problem.py"), "{}", stdout);
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--extend-exclude", "[problem"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--extend-exclude"), "{}", stderr);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_discovery_survives_symlink_loop() {
+    let dir = scratch_dir("discovery-symlink-loop");
+    fs::create_dir_all(dir.join("package")).unwrap();
+    fs::write(dir.join("package").join("problem.py"), PROBLEM_CODE).unwrap();
+    std::os::unix::fs::symlink(&dir, dir.join("package").join("loop")).unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--format", "jsonl"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "{}", stdout);
+}
+
+fn vipyrdocs_stdin(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vipyrdocs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run vipyrdocs");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_stdin() {
+    let output = vipyrdocs_stdin(&["-", "--format", "jsonl"], PROBLEM_CODE);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"code\":\"D010\""), "{}", stdout);
+
+    let output = vipyrdocs_stdin(&["-"], CLEAN_CODE);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_stdin_filename() {
+    let dir = scratch_dir("stdin-filename");
+    fs::write(
+        dir.join("vipyrdocs.toml"),
+        "[per-file-ignores]\n\"scripts/*\" = [\"DCO010\"]\n",
+    )
+    .unwrap();
+    let scripts = dir.join("scripts").join("run.py");
+    let src = dir.join("src").join("module.py");
+
+    let output = vipyrdocs_stdin(
+        &["-", "--stdin-filename", scripts.to_str().unwrap()],
+        PROBLEM_CODE,
+    );
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+
+    let output = vipyrdocs_stdin(
+        &[
+            "-",
+            "--stdin-filename",
+            src.to_str().unwrap(),
+            "--format",
+            "jsonl",
+        ],
+        PROBLEM_CODE,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("module.py"), "{}", stdout);
+
+    let output = vipyrdocs(&[src.to_str().unwrap(), "--stdin-filename", "module.py"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_multiple_paths() {
+    let dir = scratch_dir("multiple-paths");
+    fs::create_dir_all(dir.join("package")).unwrap();
+    fs::write(dir.join("package").join("problem.py"), PROBLEM_CODE).unwrap();
+    fs::write(dir.join("clean.py"), CLEAN_CODE).unwrap();
+    fs::write(dir.join("problem.py"), PROBLEM_CODE).unwrap();
+    let package = dir.join("package");
+    let nested = package.join("problem.py");
+    let problem = dir.join("problem.py");
+    let clean = dir.join("clean.py");
+
+    let output = vipyrdocs(&[
+        package.to_str().unwrap(),
+        nested.to_str().unwrap(),
+        problem.to_str().unwrap(),
+        clean.to_str().unwrap(),
+        problem.to_str().unwrap(),
+        "--format",
+        "jsonl",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2, "{}", stdout);
+
+    let output = vipyrdocs(&[clean.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let missing = dir.join("missing.py");
+    let output = vipyrdocs(&[clean.to_str().unwrap(), missing.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing.py"));
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .expect("failed to run git")
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_diff_base() {
+    let dir = scratch_dir("diff-base");
+    let legacy = "def function_1():\n    pass\n\n\ndef function_2():\n    pass\n";
+    fs::write(dir.join("legacy.py"), legacy).unwrap();
+    fs::write(dir.join("untouched.py"), PROBLEM_CODE).unwrap();
+    git(&dir, &["init", "--quiet"]);
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "--quiet", "-m", "legacy"]);
+
+    fs::write(
+        dir.join("legacy.py"),
+        legacy.replace(
+            "def function_2():\n    pass",
+            "def function_2():\n    return None",
+        ),
+    )
+    .unwrap();
+    fs::write(dir.join("new.py"), PROBLEM_CODE).unwrap();
+
+    let output = vipyrdocs(&[
+        dir.to_str().unwrap(),
+        "--diff-base",
+        "HEAD",
+        "--format",
+        "jsonl",
+        "--select",
+        "DCO010",
+    ]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", stdout);
+    assert!(lines[0].contains("\"symbol\":\"function_2\""), "{}", stdout);
+    assert!(lines[1].contains("new.py"), "{}", stdout);
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--diff-base", "no-such-rev"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_diff_base_noprefix() {
+    let dir = scratch_dir("diff-base-noprefix");
+    let legacy = "def function_1():\n    pass\n";
+    fs::write(dir.join("my module.py"), legacy).unwrap();
+    git(&dir, &["init", "--quiet"]);
+    git(&dir, &["config", "diff.noprefix", "true"]);
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "--quiet", "-m", "legacy"]);
+    fs::write(
+        dir.join("my module.py"),
+        legacy.replace("pass", "return None"),
+    )
+    .unwrap();
+
+    let output = vipyrdocs(&[
+        dir.to_str().unwrap(),
+        "--diff-base",
+        "HEAD",
+        "--format",
+        "jsonl",
+        "--select",
+        "DCO010",
+    ]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"symbol\":\"function_1\""), "{}", stdout);
+}
+
+#[test]
+fn test_diff_base_several_repositories() {
+    let dir = scratch_dir("diff-base-repositories");
+    let legacy = "def function_1():\n    pass\n";
+    for repo in ["first", "second"] {
+        let repo = dir.join(repo);
+        fs::create_dir_all(&repo).unwrap();
+        fs::write(repo.join("module.py"), legacy).unwrap();
+        git(&repo, &["init", "--quiet"]);
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "--quiet", "-m", "legacy"]);
+        fs::write(
+            repo.join("module.py"),
+            legacy.replace("pass", "return None"),
+        )
+        .unwrap();
+    }
+
+    let output = vipyrdocs(&[
+        dir.join("first").to_str().unwrap(),
+        dir.join("second").to_str().unwrap(),
+        "--diff-base",
+        "HEAD",
+        "--format",
+        "jsonl",
+        "--select",
+        "DCO010",
+    ]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2, "{}", stdout);
+    assert!(stdout.contains("second"), "{}", stdout);
+}
+
+#[test]
+fn test_baseline() {
+    let dir = scratch_dir("baseline");
+    let module = dir.join("module.py");
+    fs::write(&module, PROBLEM_CODE).unwrap();
+    let baseline = dir.join("baseline.json");
+
+    let output = vipyrdocs(&[
+        dir.to_str().unwrap(),
+        "--write-baseline",
+        baseline.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let contents = fs::read_to_string(&baseline).unwrap();
+    assert!(
+        contents.contains("\"symbol\": \"function_1\""),
+        "{}",
+        contents
+    );
+
+    // the accepted problem moved down, a new one was added
+    fs::write(
+        &module,
+        format!("\n\n{}\n\ndef function_2():\n    pass\n", PROBLEM_CODE),
+    )
+    .unwrap();
+    let args = [
+        dir.to_str().unwrap(),
+        "--baseline",
+        baseline.to_str().unwrap(),
+        "--format",
+        "jsonl",
+    ];
+    let output = vipyrdocs(&args);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "{}", stdout);
+    assert!(stdout.contains("function_2"), "{}", stdout);
+
+    fs::write(&module, CLEAN_CODE).unwrap();
+    let output = vipyrdocs(&args);
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Stale baseline entry: module.py D010 function_1"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_fix_inserts_docstring() {
+    let dir = scratch_dir("fix-docstring");
+    let module = dir.join("module.py");
+    fs::write(&module, "def function_1(arg_1):\n    return arg_1\n").unwrap();
+
+    let output = vipyrdocs(&[module.to_str().unwrap(), "--fix"]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    assert_eq!(
+        fs::read_to_string(&module).unwrap(),
+        "def function_1(arg_1):\n    \"\"\"TODO: Document function_1.\n\n    Args:\n        arg_1: TODO.\n\n    Returns:\n        TODO.\n    \"\"\"\n    return arg_1\n"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Fixed 1 problems in 1 files"), "{}", stderr);
+}
+
+#[test]
+fn test_unsafe_fixes() {
+    let dir = scratch_dir("unsafe-fixes");
+    let module = dir.join("module.py");
+    let code = "def function_1():\n    \"\"\"Docstring 1.\n\n    Raises:\n        KeyError: Never raised.\n        ValueError: Bad.\n    \"\"\"\n    raise ValueError(\"bad\")\n";
+    fs::write(&module, code).unwrap();
+
+    let output = vipyrdocs(&[module.to_str().unwrap(), "--unsafe-fixes"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+
+    let output = vipyrdocs(&[module.to_str().unwrap(), "--fix"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    assert_eq!(fs::read_to_string(&module).unwrap(), code);
+
+    let output = vipyrdocs(&[module.to_str().unwrap(), "--fix", "--unsafe-fixes"]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    assert_eq!(
+        fs::read_to_string(&module).unwrap(),
+        code.replace("        KeyError: Never raised.\n", "")
+    );
+}
+
+/// Reading `/proc/self/mem` from the start fails even as root.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_fix_reports_unreadable_file() {
+    let dir = scratch_dir("fix-unreadable");
+    let module = dir.join("module.py");
+    std::os::unix::fs::symlink("/proc/self/mem", &module).unwrap();
+    let file = module.to_str().unwrap();
+
+    for args in [
+        vec![file, "--fix"],
+        vec![file, "--diff"],
+        vec![file, "--fix", "--exit-zero"],
+    ] {
+        let output = vipyrdocs(&args);
+        assert_eq!(output.status.code(), Some(2), "{:?} {:?}", args, output);
+        let text =
+            String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+        assert!(text.contains("D004"), "{:?} {}", args, text);
+    }
+}
+
+#[test]
+fn test_diff_prints_fixes_without_writing() {
+    let dir = scratch_dir("fix-diff");
+    let module = dir.join("module.py");
+    let code = "def function_1(arg_1):\n    \"\"\"Docstring 1.\n\n    Args:\n        arg_2: Gone.\n    \"\"\"\n";
+    fs::write(&module, code).unwrap();
+    let clean = dir.join("clean.py");
+    fs::write(&clean, CLEAN_CODE).unwrap();
+    let file = module.to_str().unwrap();
+
+    let output = vipyrdocs(&[dir.to_str().unwrap(), "--diff"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    assert_eq!(fs::read_to_string(&module).unwrap(), code);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!(
+            "--- {file}\n+++ {file}\n@@ -2,5 +2,5 @@\n     \"\"\"Docstring 1.\n \n     Args:\n-        arg_2: Gone.\n+        arg_1: TODO.\n     \"\"\"\n"
+        )
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Would fix 2 problems in 1 files"),
+        "{}",
+        stderr
+    );
+
+    let output = vipyrdocs(&[clean.to_str().unwrap(), "--diff"]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    assert!(output.stdout.is_empty());
+
+    let output = vipyrdocs(&[file, "--diff", "--fix"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+}
+
+#[test]
+fn test_server_over_stdio() {
+    let messages = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"capabilities":{}}}"#,
+        r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#,
+        r#"{"jsonrpc":"2.0","method":"exit"}"#,
+    ];
+    let input: String = messages
+        .iter()
+        .map(|message| format!("Content-Length: {}\r\n\r\n{}", message.len(), message))
+        .collect();
+
+    let output = vipyrdocs_stdin(&["server"], &input);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"codeActionProvider\":true"), "{}", stdout);
+}
@@ -518,6 +518,102 @@ def test_parse(value: str, expected_docstring: docstring.Docstring):
     when: parse is called with the docstring
     then: the expected docstring information is returned.
     """
-    returned_docstring = docstring.parse(value=value)
+    returned_docstring = docstring.parse(value)
 
     assert returned_docstring == expected_docstring
+
+
+def test_parse_sections():
+    """
+    given: Google style docstring with typed arguments and a return value
+    when: parse is called with the docstring
+    then: the sections have their entries, descriptions, type annotations and line offsets.
+    """
+    returned_docstring = docstring.parse(
+        """Summary.
+
+Args:
+    arg_1 (int): The first,
+        continued.
+    arg_2: The second.
+
+Returns:
+    bool: Whether it worked.
+"""
+    )
+
+    assert [section.kind for section in returned_docstring.sections] == [None, "args", "returns"]
+    summary, args, returns = returned_docstring.sections
+    assert summary.description == "Summary."
+    assert args.name == "Args"
+    assert (args.line_offset, args.end_line_offset) == (2, 5)
+    first, second = args.entries
+    assert (first.name, first.annotation, first.description) == (
+        "arg_1",
+        "int",
+        "The first,\ncontinued.",
+    )
+    assert (first.line_offset, first.end_line_offset) == (3, 4)
+    assert (second.name, second.annotation) == ("arg_2", None)
+    (value,) = returned_docstring.returns
+    assert (value.name, value.annotation, value.description) == (None, "bool", "Whether it worked.")
+    assert returned_docstring.yields is None
+    assert returns.entries == returned_docstring.returns
+
+
+@pytest.mark.parametrize(
+    "value, style",
+    [
+        pytest.param(
+            """Summary.
+
+Parameters
+----------
+arg_1 : int
+    The first.
+
+Returns
+-------
+bool
+    Whether it worked.
+""",
+            None,
+            id="numpy",
+        ),
+        pytest.param(
+            """Summary.
+
+:param arg_1: The first.
+:type arg_1: int
+:returns: Whether it worked.
+:rtype: bool
+""",
+            "sphinx",
+            id="sphinx",
+        ),
+    ],
+)
+def test_parse_styles(value: str, style: str | None):
+    """
+    given: docstring in another style
+    when: parse is called with the docstring and style
+    then: the type annotations are read the way of the style.
+    """
+    returned_docstring = docstring.parse(value, style=style)
+
+    assert returned_docstring.args == ["arg_1"]
+    args = next(section for section in returned_docstring.sections if section.kind == "args")
+    (entry,) = args.entries
+    assert (entry.name, entry.annotation, entry.description) == ("arg_1", "int", "The first.")
+    (value,) = returned_docstring.returns
+    assert (value.annotation, value.description) == ("bool", "Whether it worked.")
+
+
+def test_parse_unknown_style():
+    """
+    given: unknown docstring style
+    when: parse is called with the style
+    then: ValueError is raised.
+    """
+    with pytest.raises(ValueError):
+        docstring.parse("Summary.", style="epytext")
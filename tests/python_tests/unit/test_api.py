@@ -0,0 +1,105 @@
+"""Unit tests for the diagnostic API."""
+
+from __future__ import annotations
+
+import pytest
+
+from ruff_docstrings_complete._core import Diagnostic, lint_paths, lint_source
+
+
+CODE = '''
+def function_1(arg_1):
+    """Docstring 1.
+
+    Args:
+        arg_2: Description.
+    """
+'''
+
+
+def test_lint_source():
+    """
+    given: source with an undocumented and an unknown argument
+    when: lint_source is called
+    then: a Diagnostic is returned for each problem
+    """
+    diagnostics = lint_source(CODE, "module.py")
+
+    assert all(isinstance(diagnostic, Diagnostic) for diagnostic in diagnostics)
+    assert [diagnostic.code for diagnostic in diagnostics] == ["D023", "D024"]
+    first = diagnostics[0]
+    assert first.symbol == "function_1"
+    assert first.file == "module.py"
+    assert first.severity == "error"
+    assert first.fixable
+    assert (first.line, first.column) <= (first.end_line, first.end_column)
+    assert str(first).startswith(f"{first.line}:{first.column} D023")
+
+
+def test_lint_source_config(tmp_path):
+    """
+    given: a configuration file that ignores DCO024
+    when: lint_source is called with it
+    then: only the other problem is returned
+    """
+    config = tmp_path / "vipyrdocs.toml"
+    config.write_text('ignore = ["DCO024"]\n')
+
+    diagnostics = lint_source(CODE, config=config)
+
+    assert [diagnostic.code for diagnostic in diagnostics] == ["D023"]
+    assert diagnostics[0].file is None
+
+
+def test_lint_source_invalid_config(tmp_path):
+    """
+    given: a configuration file with an unknown key
+    when: lint_source is called with it
+    then: ValueError is raised
+    """
+    config = tmp_path / "vipyrdocs.toml"
+    config.write_text("unknown = 1\n")
+
+    with pytest.raises(ValueError):
+        lint_source(CODE, config=config)
+
+
+def test_lint_paths(tmp_path):
+    """
+    given: a directory with a clean and a problematic file
+    when: lint_paths is called with the directory
+    then: the problems of the files are returned with their file
+    """
+    (tmp_path / "clean.py").write_text('"""Docstring."""\n')
+    (tmp_path / "module.py").write_text(CODE)
+
+    diagnostics = lint_paths([tmp_path])
+
+    assert [(diagnostic.code, diagnostic.file) for diagnostic in diagnostics] == [
+        ("D023", str(tmp_path / "module.py")),
+        ("D024", str(tmp_path / "module.py")),
+    ]
+
+
+def test_lint_paths_missing(tmp_path):
+    """
+    given: a path that does not exist
+    when: lint_paths is called with it
+    then: FileNotFoundError is raised
+    """
+    with pytest.raises(FileNotFoundError):
+        lint_paths([tmp_path / "missing.py"])
+
+
+def test_lint_paths_different_configs(tmp_path):
+    """
+    given: two directories with a configuration file each
+    when: lint_paths is called with both
+    then: ValueError is raised
+    """
+    for name in ("first", "second"):
+        (tmp_path / name).mkdir()
+        (tmp_path / name / "vipyrdocs.toml").write_text("")
+
+    with pytest.raises(ValueError):
+        lint_paths([tmp_path / "first", tmp_path / "second"])